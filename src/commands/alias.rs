@@ -0,0 +1,83 @@
+use anyhow::{Result, anyhow};
+use crate::config;
+use serde_json::json;
+use crate::options::{output, symbols};
+use crate::options::theme::Themed;
+use crate::utils::{self, download};
+use crate::say;
+
+pub fn execute(name: Option<&str>, version: Option<&str>) -> Result<()> {
+    match (name, version) {
+        (Some(name), Some(version)) => set(name, version),
+        (Some(name), None) => show(name),
+        _ => list(),
+    }
+}
+
+fn set(name: &str, version: &str) -> Result<()> {
+    validate_name(name)?;
+
+    let mut config = config::load_config()?;
+    let target = version.trim().trim_start_matches('v').to_string();
+    if config.aliases.contains_key(&target) {
+        return Err(anyhow!("{} is itself an alias; point {} at a version or channel instead", target, name));
+    }
+    if !download::is_alias(&target) {
+        utils::parse_version_spec(&target)?;
+    }
+
+    config.aliases.insert(name.to_string(), target.clone());
+    config::save_config(&config)?;
+
+    say!("Alias {} {} {}", name.info(), symbols::arrow(), target.success());
+    Ok(())
+}
+
+fn show(name: &str) -> Result<()> {
+    let config = config::load_config()?;
+    let target = config.aliases.get(name)
+        .ok_or_else(|| anyhow!("No alias named {}", name))?;
+
+    if output::is_json() {
+        return output::print_json(&json!({ "name": name, "target": target }));
+    }
+    println!("{}", target);
+    Ok(())
+}
+
+fn list() -> Result<()> {
+    let config = config::load_config()?;
+    if output::is_json() {
+        return output::print_json(&config.aliases);
+    }
+    if config.aliases.is_empty() {
+        say!("No aliases defined. Create one with 'node-spark alias <name> <version>'.");
+        return Ok(());
+    }
+
+    for (name, target) in &config.aliases {
+        say!("{:<12} {} {}", name.info(), symbols::arrow(), target);
+    }
+    Ok(())
+}
+
+pub fn unalias(name: &str) -> Result<()> {
+    let mut config = config::load_config()?;
+    if config.aliases.remove(name).is_none() {
+        return Err(anyhow!("No alias named {}", name));
+    }
+    config::save_config(&config)?;
+
+    say!("Removed alias {}", name.info());
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty() || name == "-" || name.contains(char::is_whitespace) {
+        return Err(anyhow!("Invalid alias name: {:?}", name));
+    }
+    if utils::parse_version_spec(name).is_ok() {
+        return Err(anyhow!("{} looks like a version; pick an alias name that is not a version or range", name));
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use anyhow::{Result, anyhow};
+use serde_json::{json, Value};
+use crate::config;
+use crate::options::{output, symbols, verbose};
+use crate::options::theme::Themed;
+use crate::utils::{self, download};
+use crate::say;
+
+struct Audit {
+    version: String,
+    installed: bool,
+    fixed_in: Option<String>,
+}
+
+pub fn execute(specs: &[String], as_json: bool) -> Result<()> {
+    verbose::log("Executing audit-versions command");
+    let dirs = config::get_dirs()?;
+    let installed = utils::installed_releases(&dirs.versions_dir)?;
+
+    if !as_json {
+        say!("Checking versions against the Node.js security releases...");
+    }
+    let index = download::get_remote_index()?;
+
+    let versions = if specs.is_empty() {
+        installed.clone()
+    } else {
+        specs.iter()
+            .map(|spec| match utils::parse_version(spec) {
+                Ok(version) => Ok(version),
+                Err(_) => download::resolve_remote(spec, &index)
+                    .ok_or_else(|| anyhow!("No Node.js release matches {}", spec)),
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let audits: Vec<Audit> = versions.into_iter()
+        .map(|version| Audit {
+            installed: installed.contains(&version),
+            fixed_in: download::missing_security_release(&version, &index).map(|fix| fix.version.clone()),
+            version,
+        })
+        .collect();
+    let vulnerable = audits.iter().filter(|audit| audit.fixed_in.is_some()).count();
+
+    if as_json {
+        let entries: Vec<Value> = audits.iter().map(|audit| json!({
+            "version": audit.version,
+            "installed": audit.installed,
+            "vulnerable": audit.fixed_in.is_some(),
+            "fixed_in": audit.fixed_in,
+        })).collect();
+        output::print_json(&entries)?;
+    } else if audits.is_empty() {
+        say!("No installed versions to audit");
+        return Ok(());
+    } else {
+        say!();
+        for audit in &audits {
+            match audit.fixed_in {
+                Some(ref fix) => say!("  {:<10} {} {}", audit.version, symbols::shield().error(),
+                    format!("superseded by security release {}", fix).error()),
+                None => say!("  {:<10} {}", audit.version, "no known security releases after it".success()),
+            }
+        }
+    }
+
+    if vulnerable > 0 {
+        return Err(anyhow!("{} of {} version(s) are missing security releases; run 'node-spark upgrade' or install the fixed versions", vulnerable, audits.len()));
+    }
+    if !as_json {
+        say!("\n{}", "No known vulnerable versions".success());
+    }
+    Ok(())
+}
+
+pub fn warn_if_vulnerable(version: &str) {
+    let Some(index) = download::cached_remote_index() else { return };
+    if let Some(fix) = download::missing_security_release(version, &index) {
+        say!("{} {} Node.js {} is superseded by security release {}; consider installing that instead",
+            "Warning:".warning(), symbols::shield(), version, fix.version.success());
+    }
+}
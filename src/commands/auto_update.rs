@@ -0,0 +1,239 @@
+use anyhow::{Result, anyhow};
+use clap::ValueEnum;
+use std::env;
+use std::process::Command;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::{fs, path::PathBuf};
+use crate::config;
+use crate::options::verbose;
+use crate::options::theme::Themed;
+use crate::say;
+
+const TASK_NAME: &str = "node-spark-auto-update";
+// What every scheduler runs, after the node-spark executable
+const UPDATE_ARGS: [&str; 3] = ["upgrade-node", "--lts", "--yes"];
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Cadence {
+    Daily,
+    Weekly,
+}
+
+impl Cadence {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Cadence::Daily => "daily",
+            Cadence::Weekly => "weekly",
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn seconds(&self) -> u64 {
+        match self {
+            Cadence::Daily => 24 * 60 * 60,
+            Cadence::Weekly => 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+pub fn enable(cadence: Cadence) -> Result<()> {
+    let executable = env::current_exe()?;
+    verbose::log(&format!("Scheduling {} {} ({})", executable.display(), UPDATE_ARGS.join(" "), cadence.as_str()));
+
+    install_schedule(&executable, cadence)?;
+
+    let mut config = config::load_config()?;
+    config.auto_update = Some(cadence.as_str().to_string());
+    config::save_config(&config)?;
+
+    say!("Automatic Node.js patch updates enabled ({})", cadence.as_str().success());
+    Ok(())
+}
+
+pub fn disable() -> Result<()> {
+    remove_schedule()?;
+
+    let mut config = config::load_config()?;
+    config.auto_update = None;
+    config::save_config(&config)?;
+
+    say!("Automatic Node.js patch updates disabled");
+    Ok(())
+}
+
+pub fn status() -> Result<()> {
+    let config = config::load_config()?;
+
+    match config.auto_update {
+        Some(ref cadence) if schedule_installed() => {
+            say!("Automatic updates: {} ({})", "enabled".success(), cadence);
+        }
+        Some(ref cadence) => {
+            say!("Automatic updates: {} ({} configured, but the scheduled task is missing)",
+                "broken".error(), cadence);
+            say!("Run 'node-spark auto-update enable' to recreate it.");
+        }
+        None => say!("Automatic updates: {}", "disabled".warning()),
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn unit_dir() -> Result<PathBuf> {
+    let base = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set"))?).join(".config"),
+    };
+    Ok(base.join("systemd").join("user"))
+}
+
+#[cfg(target_os = "linux")]
+fn install_schedule(executable: &std::path::Path, cadence: Cadence) -> Result<()> {
+    let unit_dir = unit_dir()?;
+    fs::create_dir_all(&unit_dir)?;
+
+    fs::write(unit_dir.join(format!("{}.service", TASK_NAME)), format!(
+        "[Unit]\nDescription=node-spark Node.js patch update\n\n[Service]\nType=oneshot\nExecStart=\"{}\" {}\n",
+        executable.display(), UPDATE_ARGS.join(" ")
+    ))?;
+    fs::write(unit_dir.join(format!("{}.timer", TASK_NAME)), format!(
+        "[Unit]\nDescription=Run node-spark Node.js patch update {}\n\n[Timer]\nOnCalendar={}\nPersistent=true\n\n[Install]\nWantedBy=timers.target\n",
+        cadence.as_str(), cadence.as_str()
+    ))?;
+
+    run_system_command("systemctl", &["--user", "daemon-reload"])?;
+    run_system_command("systemctl", &["--user", "enable", "--now", &format!("{}.timer", TASK_NAME)])
+}
+
+#[cfg(target_os = "linux")]
+fn remove_schedule() -> Result<()> {
+    let unit_dir = unit_dir()?;
+    let timer = unit_dir.join(format!("{}.timer", TASK_NAME));
+
+    if timer.exists() {
+        run_system_command("systemctl", &["--user", "disable", "--now", &format!("{}.timer", TASK_NAME)])?;
+    }
+    for extension in ["timer", "service"] {
+        let path = unit_dir.join(format!("{}.{}", TASK_NAME, extension));
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn schedule_installed() -> bool {
+    unit_dir().map(|dir| dir.join(format!("{}.timer", TASK_NAME)).exists()).unwrap_or(false)
+}
+
+#[cfg(target_os = "macos")]
+fn plist_path() -> Result<PathBuf> {
+    let home = env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set"))?;
+    Ok(PathBuf::from(home).join("Library").join("LaunchAgents").join("com.node-spark.auto-update.plist"))
+}
+
+#[cfg(target_os = "macos")]
+fn install_schedule(executable: &std::path::Path, cadence: Cadence) -> Result<()> {
+    let plist = plist_path()?;
+    if let Some(parent) = plist.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let arguments: String = UPDATE_ARGS.iter()
+        .map(|arg| format!("\n        <string>{}</string>", arg))
+        .collect();
+    fs::write(&plist, format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>com.node-spark.auto-update</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{}</string>{}
+    </array>
+    <key>StartInterval</key>
+    <integer>{}</integer>
+</dict>
+</plist>
+"#,
+        executable.display(), arguments, cadence.seconds()
+    ))?;
+
+    run_system_command("launchctl", &["load", "-w", &plist.to_string_lossy()])
+}
+
+#[cfg(target_os = "macos")]
+fn remove_schedule() -> Result<()> {
+    let plist = plist_path()?;
+    if plist.exists() {
+        run_system_command("launchctl", &["unload", "-w", &plist.to_string_lossy()])?;
+        fs::remove_file(plist)?;
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn schedule_installed() -> bool {
+    plist_path().map(|path| path.exists()).unwrap_or(false)
+}
+
+#[cfg(target_os = "windows")]
+fn install_schedule(executable: &std::path::Path, cadence: Cadence) -> Result<()> {
+    let schedule = match cadence {
+        Cadence::Daily => "DAILY",
+        Cadence::Weekly => "WEEKLY",
+    };
+    let task = format!("\"{}\" {}", executable.display(), UPDATE_ARGS.join(" "));
+    run_system_command("schtasks", &["/Create", "/F", "/SC", schedule, "/TN", TASK_NAME, "/TR", &task])
+}
+
+#[cfg(target_os = "windows")]
+fn remove_schedule() -> Result<()> {
+    if schedule_installed() {
+        run_system_command("schtasks", &["/Delete", "/F", "/TN", TASK_NAME])?;
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn schedule_installed() -> bool {
+    Command::new("schtasks")
+        .args(["/Query", "/TN", TASK_NAME])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn install_schedule(_executable: &std::path::Path, _cadence: Cadence) -> Result<()> {
+    Err(anyhow!("Automatic updates are not supported on this platform"))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn remove_schedule() -> Result<()> {
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn schedule_installed() -> bool {
+    false
+}
+
+fn run_system_command(program: &str, args: &[&str]) -> Result<()> {
+    verbose::log(&format!("Running {} {}", program, args.join(" ")));
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| anyhow!("Failed to run {}: {}", program, e))?;
+
+    if !output.status.success() {
+        return Err(anyhow!("{} failed: {}", program, String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    Ok(())
+}
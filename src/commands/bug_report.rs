@@ -0,0 +1,108 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::env;
+use crate::commands::doctor;
+use crate::config;
+use crate::options::{output, verbose};
+use crate::utils;
+use crate::say;
+
+const REDACTED: &str = "<redacted>";
+
+pub fn execute(as_json: bool) -> Result<()> {
+    verbose::log("Executing bug-report command");
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+
+    let report = json!({
+        "node_spark": {
+            "version": env!("CARGO_PKG_VERSION"),
+            "profile": if cfg!(debug_assertions) { "debug" } else { "release" },
+            "executable": env::current_exe().ok(),
+        },
+        "system": {
+            "os": env::consts::OS,
+            "arch": env::consts::ARCH,
+            "family": env::consts::FAMILY,
+            "shell": env::var("SHELL").ok(),
+        },
+        "versions": {
+            "active": config.active_version,
+            "installed": utils::installed_versions(&dirs.versions_dir)?,
+            "bin_dir_on_path": utils::is_on_path(&dirs.bin_dir),
+        },
+        "config": redact(serde_json::to_value(&config)?),
+        "doctor": doctor::checks_json()?,
+    });
+
+    if as_json {
+        output::print_json(&report)?;
+    } else {
+        print_markdown(&report)?;
+    }
+
+    Ok(())
+}
+
+fn print_markdown(report: &Value) -> Result<()> {
+    say!("## node-spark bug report\n");
+
+    for section in ["node_spark", "system", "versions"] {
+        say!("### {}\n", section);
+        if let Some(fields) = report[section].as_object() {
+            for (key, value) in fields {
+                say!("- **{}**: {}", key, plain(value));
+            }
+        }
+        say!();
+    }
+
+    say!("### config\n");
+    say!("```json\n{}\n```\n", serde_json::to_string_pretty(&report["config"])?);
+
+    say!("### doctor\n");
+    if let Some(checks) = report["doctor"].as_array() {
+        for check in checks {
+            say!("- `{}` {}: {}", plain(&check["id"]), plain(&check["status"]), plain(&check["details"]));
+        }
+    }
+
+    Ok(())
+}
+
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "none".to_string(),
+        Value::Array(items) => items.iter().map(plain).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+fn redact(mut config: Value) -> Value {
+    if let Some(version_env) = config.get_mut("version_env").and_then(Value::as_object_mut) {
+        for vars in version_env.values_mut().filter_map(Value::as_object_mut) {
+            vars.values_mut().for_each(|value| *value = json!(REDACTED));
+        }
+    }
+
+    redact_credentials(&mut config);
+    config
+}
+
+fn redact_credentials(value: &mut Value) {
+    match value {
+        Value::String(s) => {
+            if let Ok(mut url) = reqwest::Url::parse(s) {
+                if !url.username().is_empty() || url.password().is_some() {
+                    let _ = url.set_username("redacted");
+                    let _ = url.set_password(None);
+                    *s = url.to_string();
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_credentials),
+        Value::Object(fields) => fields.values_mut().for_each(redact_credentials),
+        _ => {}
+    }
+}
@@ -0,0 +1,155 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use crate::config::{self, NodeSparkDirs};
+use crate::options::{output, verbose};
+use crate::options::theme::Themed;
+use crate::utils::{self, archive_cache, disk, temp};
+use crate::say;
+
+struct CacheEntry {
+    path: PathBuf,
+    size: u64,
+    modified: u64,
+    partial: bool,
+}
+
+impl CacheEntry {
+    fn name(&self) -> String {
+        self.path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default()
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "name": self.name(),
+            "path": self.path,
+            "bytes": self.size,
+            "modified": self.modified,
+            "partial": self.partial,
+        })
+    }
+}
+
+pub fn list() -> Result<()> {
+    verbose::log("Executing cache list command");
+    let dirs = config::get_dirs()?;
+    let entries = entries(&dirs);
+
+    if output::is_json() {
+        return output::print_json(&entries.iter().map(CacheEntry::to_json).collect::<Vec<_>>());
+    }
+
+    if entries.is_empty() {
+        say!("The cache is empty");
+        return Ok(());
+    }
+
+    for entry in &entries {
+        let kind = if entry.partial { format!(" {}", "(partial download)".warning()) } else { String::new() };
+        say!(
+            "  {:<40} {:>10}  {}{}",
+            entry.name(),
+            disk::format_size(entry.size),
+            utils::format_age(entry.modified).muted(),
+            kind
+        );
+    }
+    Ok(())
+}
+
+pub fn size() -> Result<()> {
+    verbose::log("Executing cache size command");
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+    let entries = entries(&dirs);
+
+    let (partial, archives): (Vec<&CacheEntry>, Vec<&CacheEntry>) = entries.iter().partition(|entry| entry.partial);
+    let archive_bytes: u64 = archives.iter().map(|entry| entry.size).sum();
+    let partial_bytes: u64 = partial.iter().map(|entry| entry.size).sum();
+
+    if output::is_json() {
+        return output::print_json(&json!({
+            "archives": archives.len(),
+            "archive_bytes": archive_bytes,
+            "partial": partial.len(),
+            "partial_bytes": partial_bytes,
+            "max_bytes": config.cache_max_mb.map(archive_cache::max_bytes),
+        }));
+    }
+
+    let limit = match config.cache_max_mb {
+        Some(max_mb) => format!(" of {}", disk::format_size(archive_cache::max_bytes(max_mb))),
+        None => String::new(),
+    };
+    say!("  Archives:          {} file(s), {}{}", archives.len(), disk::format_size(archive_bytes), limit);
+    say!("  Partial downloads: {} file(s), {}", partial.len(), disk::format_size(partial_bytes));
+    say!("  Total:             {}", disk::format_size(archive_bytes + partial_bytes).success());
+    Ok(())
+}
+
+pub fn clear() -> Result<()> {
+    verbose::log("Executing cache clear command");
+    let dirs = config::get_dirs()?;
+    let orphaned: Vec<PathBuf> = temp::stale_entries(&dirs, temp::STALE_AFTER).into_iter()
+        .filter(|leftover| leftover.kind == temp::LeftoverKind::Download)
+        .map(|leftover| leftover.path)
+        .collect();
+    let (entries, in_flight): (Vec<CacheEntry>, Vec<CacheEntry>) = entries(&dirs).into_iter()
+        .partition(|entry| !entry.partial || orphaned.contains(&entry.path));
+
+    if !in_flight.is_empty() {
+        say!("Keeping {} recent partial download(s) that may belong to a running install", in_flight.len());
+    }
+    if entries.is_empty() {
+        say!("The cache is already empty");
+        return Ok(());
+    }
+
+    let mut freed = 0;
+    for entry in &entries {
+        verbose::log(&format!("Removing {}", entry.path.display()));
+        if entry.path.is_dir() {
+            fs::remove_dir_all(&entry.path)?;
+        } else {
+            fs::remove_file(&entry.path)?;
+        }
+        freed += entry.size;
+    }
+
+    say!("Removed {} cached file(s), freed {}", entries.len(), disk::format_size(freed).success());
+    Ok(())
+}
+
+fn entries(dirs: &NodeSparkDirs) -> Vec<CacheEntry> {
+    let mut entries = read_entries(&dirs.cache_dir, false);
+    entries.extend(read_entries(&dirs.config_dir.join("temp"), true));
+    entries
+}
+
+fn read_entries(dir: &Path, partial: bool) -> Vec<CacheEntry> {
+    let mut entries: Vec<CacheEntry> = fs::read_dir(dir)
+        .map(|entries| entries.flatten().collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entry| {
+            let path = entry.path();
+            let metadata = entry.metadata().ok();
+            let size = match metadata {
+                Some(ref metadata) if metadata.is_dir() => disk::dir_size(&path),
+                Some(ref metadata) => metadata.len(),
+                None => 0,
+            };
+            let modified = metadata
+                .and_then(|metadata| metadata.modified().ok())
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .map(|age| age.as_secs())
+                .unwrap_or_default();
+            CacheEntry { path, size, modified, partial }
+        })
+        .collect();
+
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.modified));
+    entries
+}
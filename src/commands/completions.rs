@@ -0,0 +1,117 @@
+use anyhow::{Result, anyhow};
+use clap::CommandFactory;
+use clap_complete::Shell as CompletionShell;
+use std::collections::BTreeSet;
+use crate::commands::env::Shell;
+use crate::config;
+use crate::options::Cli;
+use crate::utils::{self, download};
+
+const INSTALLED_COMMANDS: [&str; 6] = ["use", "remove", "exec", "verify", "env", "which"];
+const REMOTE_COMMANDS: [&str; 3] = ["install", "url", "pin"];
+const RECENT_REMOTE_VERSIONS: usize = 50;
+
+pub fn execute(shell: Shell) -> Result<()> {
+    let target = match shell {
+        Shell::Bash => CompletionShell::Bash,
+        Shell::Zsh => CompletionShell::Zsh,
+        Shell::Fish => CompletionShell::Fish,
+        Shell::Powershell => CompletionShell::PowerShell,
+        Shell::Cmd => return Err(anyhow!("cmd does not support completion scripts")),
+    };
+
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    let mut script = Vec::new();
+    clap_complete::generate(target, &mut command, name.clone(), &mut script);
+    let script = String::from_utf8(script)?;
+
+    let script = match shell {
+        Shell::Bash => with_bash_versions(&script, &name),
+        Shell::Zsh => with_zsh_versions(&script, &name),
+        Shell::Fish => with_fish_versions(&script, &name),
+        _ => script,
+    };
+
+    print!("{}", script);
+    Ok(())
+}
+
+pub fn helper(words: &[String]) -> Result<()> {
+    let subcommand = words.iter()
+        .map(String::as_str)
+        .find(|word| INSTALLED_COMMANDS.contains(word) || REMOTE_COMMANDS.contains(word));
+
+    let mut candidates = match subcommand {
+        Some(subcommand) if REMOTE_COMMANDS.contains(&subcommand) => remote_candidates(),
+        Some(_) => utils::installed_versions(&config::get_dirs()?.versions_dir)?,
+        None => return Ok(()),
+    };
+    candidates.extend(config::load_config()?.aliases.into_keys());
+
+    for candidate in candidates {
+        println!("{}", candidate);
+    }
+    Ok(())
+}
+
+fn remote_candidates() -> Vec<String> {
+    let index = download::cached_remote_index().unwrap_or_default();
+
+    let mut candidates = vec!["latest".to_string(), "lts".to_string()];
+    let codenames: BTreeSet<String> = index.iter()
+        .filter_map(|remote| remote.lts.as_ref())
+        .map(|codename| format!("lts/{}", codename.to_lowercase()))
+        .collect();
+    candidates.extend(codenames);
+    candidates.extend(index.into_iter().take(RECENT_REMOTE_VERSIONS).map(|remote| remote.version));
+
+    candidates
+}
+
+fn with_bash_versions(script: &str, name: &str) -> String {
+    let function = format!("_{}", name.replace('-', "__"));
+    let dynamic = format!(r#"_node_spark_dynamic() {{
+    if [[ ${{COMP_CWORD}} -eq 2 && ${{COMP_WORDS[COMP_CWORD]}} != -* ]]; then
+        local versions
+        versions="$({name} completion-helper "${{COMP_WORDS[1]}}" 2>/dev/null)"
+        if [[ -n "${{versions}}" ]]; then
+            COMPREPLY=( $(compgen -W "${{versions}}" -- "${{COMP_WORDS[COMP_CWORD]}}") )
+            return 0
+        fi
+    fi
+    {function} "$@"
+}}
+
+"#);
+
+    let registration = script.rfind("if [[ \"${BASH_VERSINFO").unwrap_or(script.len());
+    let (body, tail) = script.split_at(registration);
+    format!("{}{}{}", body, dynamic, tail.replace(&format!("complete -F {} ", function), "complete -F _node_spark_dynamic "))
+}
+
+fn with_zsh_versions(script: &str, name: &str) -> String {
+    let function = format!(r#"(( $+functions[_node_spark_versions] )) ||
+_node_spark_versions() {{
+    local -a versions
+    versions=(${{(f)"$({name} completion-helper ${{words[@]}} 2>/dev/null)"}})
+    compadd -a versions
+}}
+
+"#);
+
+    let script = script.replace(":version:_default'", ":version:_node_spark_versions'");
+    let registration = script.find("if [ \"$funcstack[1]\"").unwrap_or(script.len());
+    let (body, tail) = script.split_at(registration);
+    format!("{}{}{}", body, function, tail)
+}
+
+fn with_fish_versions(script: &str, name: &str) -> String {
+    format!(
+        "{}complete -c {name} -n \"__fish_seen_subcommand_from {}\" -f -a \"({name} completion-helper use)\"\n\
+         complete -c {name} -n \"__fish_seen_subcommand_from {}\" -f -a \"({name} completion-helper install)\"\n",
+        script,
+        INSTALLED_COMMANDS.join(" "),
+        REMOTE_COMMANDS.join(" "),
+    )
+}
@@ -0,0 +1,191 @@
+use anyhow::{Result, anyhow};
+use std::path::{Path, PathBuf};
+use crate::commands::resolve::Resolution;
+use crate::config;
+
+#[cfg(unix)]
+use {
+    std::collections::HashMap,
+    std::fs,
+    std::io::{BufRead, BufReader, Write},
+    std::os::unix::net::{UnixListener, UnixStream},
+    std::time::{Duration, Instant, SystemTime},
+    crate::commands::resolve,
+    crate::config::Config,
+    crate::options::verbose,
+    crate::options::theme::Themed,
+    crate::utils,
+};
+use crate::say;
+
+#[cfg(unix)]
+const QUERY_TIMEOUT: Duration = Duration::from_millis(200);
+#[cfg(unix)]
+const PROJECT_TTL: Duration = Duration::from_secs(2);
+
+fn socket_path() -> Result<PathBuf> {
+    Ok(config::get_dirs()?.config_dir.join("resolver.sock"))
+}
+
+#[cfg(unix)]
+fn request(line: &str) -> Option<String> {
+    let mut stream = UnixStream::connect(socket_path().ok()?).ok()?;
+    stream.set_read_timeout(Some(QUERY_TIMEOUT)).ok()?;
+    stream.set_write_timeout(Some(QUERY_TIMEOUT)).ok()?;
+    writeln!(stream, "{}", line).ok()?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).ok()?;
+    Some(reply.trim_end().to_string())
+}
+
+#[cfg(unix)]
+pub fn query(dir: &Path) -> Option<Resolution> {
+    Resolution::decode(&request(&format!("resolve {}", dir.display()))?)
+}
+
+#[cfg(not(unix))]
+pub fn query(_dir: &Path) -> Option<Resolution> {
+    None
+}
+
+#[cfg(unix)]
+struct Cache {
+    config: Config,
+    config_mtime: Option<SystemTime>,
+    installed: Vec<String>,
+    versions_mtime: Option<SystemTime>,
+    projects: HashMap<PathBuf, (Instant, Resolution)>,
+}
+
+#[cfg(unix)]
+impl Cache {
+    fn refresh(&mut self, dirs: &config::NodeSparkDirs) -> Result<()> {
+        let config_mtime = mtime(&dirs.config_dir.join("config.json"));
+        let versions_mtime = mtime(&dirs.versions_dir);
+
+        if config_mtime != self.config_mtime || versions_mtime != self.versions_mtime {
+            verbose::log("Reloading config and installed versions");
+            self.config = config::load_config()?;
+            self.installed = utils::installed_versions(&dirs.versions_dir)?;
+            self.config_mtime = config_mtime;
+            self.versions_mtime = versions_mtime;
+            self.projects.clear();
+        }
+
+        Ok(())
+    }
+
+    fn resolve(&mut self, dir: &Path) -> Result<Resolution> {
+        if let Some((at, resolution)) = self.projects.get(dir) {
+            if at.elapsed() < PROJECT_TTL {
+                return Ok(resolution.clone());
+            }
+        }
+
+        let resolution = resolve::resolve(dir, &self.config, &self.installed)?;
+        self.projects.insert(dir.to_path_buf(), (Instant::now(), resolution.clone()));
+        Ok(resolution)
+    }
+}
+
+#[cfg(unix)]
+fn mtime(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(unix)]
+pub fn run() -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let path = socket_path()?;
+
+    if request("ping").is_some() {
+        return Err(anyhow!("The resolver daemon is already running ({})", path.display()));
+    }
+    if path.exists() {
+        fs::remove_file(&path)?;
+    }
+
+    let listener = UnixListener::bind(&path)?;
+    say!("Resolver daemon listening on {}", path.display().to_string().info());
+
+    let mut cache = Cache {
+        config: config::load_config()?,
+        config_mtime: None,
+        installed: Vec::new(),
+        versions_mtime: None,
+        projects: HashMap::new(),
+    };
+    cache.refresh(&dirs)?;
+
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                verbose::log(&format!("Failed to accept connection: {}", e));
+                continue;
+            }
+        };
+        stream.set_read_timeout(Some(QUERY_TIMEOUT))?;
+
+        let mut line = String::new();
+        if BufReader::new(&stream).read_line(&mut line).is_err() {
+            continue;
+        }
+        let line = line.trim_end();
+
+        let reply = if line == "ping" {
+            "pong".to_string()
+        } else if line == "stop" {
+            let _ = writeln!(stream, "bye");
+            break;
+        } else if let Some(dir) = line.strip_prefix("resolve ") {
+            cache.refresh(&dirs)
+                .and_then(|_| cache.resolve(Path::new(dir)))
+                .map(|resolution| resolution.encode())
+                .unwrap_or_else(|e| format!("error\t{}", e))
+        } else {
+            format!("error\tunknown request: {}", line)
+        };
+
+        let _ = writeln!(stream, "{}", reply);
+    }
+
+    fs::remove_file(&path)?;
+    say!("Resolver daemon stopped");
+    Ok(())
+}
+
+#[cfg(unix)]
+pub fn stop() -> Result<()> {
+    match request("stop") {
+        Some(_) => say!("Resolver daemon stopped"),
+        None => say!("Resolver daemon is not running"),
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+pub fn status() -> Result<()> {
+    let path = socket_path()?;
+    match request("ping") {
+        Some(_) => say!("Resolver daemon: {} ({})", "running".success(), path.display()),
+        None => say!("Resolver daemon: {}", "not running".warning()),
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn run() -> Result<()> {
+    Err(anyhow!("The resolver daemon is only supported on Unix-like systems (socket {})", socket_path()?.display()))
+}
+
+#[cfg(not(unix))]
+pub fn stop() -> Result<()> {
+    run()
+}
+
+#[cfg(not(unix))]
+pub fn status() -> Result<()> {
+    run()
+}
@@ -0,0 +1,16 @@
+use anyhow::Result;
+use crate::commands::{exec, install, run};
+
+pub fn execute(node: Option<&str>, command: &[String]) -> Result<()> {
+    let version = match node {
+        Some(spec) => install::ensure_installed(spec.trim_start_matches('v'))?,
+        None => run::resolve_project_version()?,
+    };
+
+    let npx_cmd = if cfg!(target_os = "windows") { "npx.cmd" } else { "npx" };
+
+    let mut npx_command = vec![npx_cmd.to_string(), "--yes".to_string()];
+    npx_command.extend(command.iter().cloned());
+
+    exec::run_under(&version, &[], &npx_command)
+}
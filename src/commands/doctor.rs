@@ -0,0 +1,485 @@
+use anyhow::{Result, anyhow};
+use serde_json::{json, Value};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use crate::error::NodeSparkError;
+use crate::config::{self, Config, NodeSparkDirs};
+use crate::options::{output, symbols, verbose};
+use crate::commands::install;
+use crate::utils::{self, http, shim, temp};
+use crate::options::theme::Themed;
+use crate::say;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const TLS_REMEDIATION: &str = "If the certificate is not trusted, install your organization's root CA \
+    into the system trust store. Timeouts usually point at a proxy or firewall.";
+
+struct Check {
+    id: &'static str,
+    name: String,
+    outcome: Outcome,
+    fix: &'static str,
+}
+
+enum Outcome {
+    Pass(String),
+    Fail(String),
+    Skip(String),
+}
+
+impl Check {
+    fn new(id: &'static str, name: impl Into<String>, result: Result<String>, fix: &'static str) -> Self {
+        let outcome = match result {
+            Ok(detail) => Outcome::Pass(detail),
+            Err(e) => Outcome::Fail(e.to_string()),
+        };
+        Check { id, name: name.into(), outcome, fix }
+    }
+
+    fn skipped(id: &'static str, name: impl Into<String>, reason: &str, fix: &'static str) -> Self {
+        Check { id, name: name.into(), outcome: Outcome::Skip(reason.to_string()), fix }
+    }
+
+    fn failed(&self) -> bool {
+        matches!(self.outcome, Outcome::Fail(_))
+    }
+
+    fn to_json(&self) -> Value {
+        let (status, details) = match self.outcome {
+            Outcome::Pass(ref detail) => ("pass", detail),
+            Outcome::Fail(ref detail) => ("fail", detail),
+            Outcome::Skip(ref detail) => ("skip", detail),
+        };
+
+        json!({
+            "id": self.id,
+            "name": self.name,
+            "status": status,
+            "details": details,
+            "fix": if self.failed() { Some(self.fix) } else { None },
+        })
+    }
+}
+
+pub fn execute(as_json: bool, fix: bool) -> Result<()> {
+    verbose::log("Executing doctor command");
+    let dirs = config::get_dirs()?;
+    if fix {
+        apply_fixes(&dirs)?;
+    }
+    if !as_json {
+        say!("Checking the node-spark installation...\n");
+    }
+
+    let mut checks = general_checks(&dirs);
+    checks.extend(network_checks());
+    finish(&checks, as_json, "doctor")
+}
+
+pub fn network(as_json: bool) -> Result<()> {
+    verbose::log("Executing doctor network command");
+    if !as_json {
+        say!("Checking network access to {}...\n", utils::dist_url());
+    }
+
+    finish(&network_checks(), as_json, "network")
+}
+
+pub fn checks_json() -> Result<Vec<Value>> {
+    let dirs = config::get_dirs()?;
+    let mut checks = general_checks(&dirs);
+    checks.extend(network_checks());
+    Ok(checks.iter().map(Check::to_json).collect())
+}
+
+fn general_checks(dirs: &NodeSparkDirs) -> Vec<Check> {
+    let mut checks = vec![Check::new(
+        "setup.path",
+        "bin directory on PATH",
+        check_bin_dir(dirs),
+        "Run 'node-spark setup' to add the bin directory to PATH in your shell startup file.",
+    )];
+
+    let config_name = "Configuration file";
+    let config_fix = "Fix the JSON syntax in config.json, or run 'node-spark doctor --fix' to start from defaults.";
+    let links_name = "node, npm and npx links";
+    let links_fix = "Run 'node-spark doctor --fix' to recreate the links.";
+    match parse_config(dirs) {
+        Ok(config) => {
+            checks.push(Check::new("config.parse", config_name, Ok(dirs.config_dir.join("config.json").display().to_string()), config_fix));
+            checks.push(Check::new("setup.links", links_name, check_links(dirs, &config), links_fix));
+        }
+        Err(e) => {
+            checks.push(Check::new("config.parse", config_name, Err(e), config_fix));
+            checks.push(Check::skipped("setup.links", links_name, "configuration could not be read", links_fix));
+        }
+    }
+
+    checks.push(Check::new(
+        "permissions.write",
+        "Write access to node-spark directories",
+        check_writable(dirs),
+        "Make sure the node-spark directories are owned by your user, or fix their permissions.",
+    ));
+
+    checks.push(Check::new(
+        "conflicts.managers",
+        "Other Node.js version managers",
+        check_conflicts(),
+        "Remove the other version manager from your shell profile so its node does not shadow node-spark's.",
+    ));
+
+    checks
+}
+
+fn network_checks() -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    let dist = utils::dist_url();
+    let parsed = reqwest::Url::parse(&dist).ok();
+    let host = parsed.as_ref().and_then(|url| url.host_str()).unwrap_or("nodejs.org").to_string();
+    let port = parsed.as_ref().and_then(|url| url.port_or_known_default()).unwrap_or(443);
+    let scheme = parsed.as_ref().map(|url| url.scheme()).unwrap_or("https");
+
+    // Through a proxy only the proxy has to be reachable; the mirror may not even resolve locally
+    let proxy = proxy_for(scheme, &host);
+    let (host, port, dns_name, tcp_name, dns_fix, tcp_fix) = match proxy {
+        Some(proxy) => (
+            proxy.host.clone(),
+            proxy.port,
+            format!("DNS lookup for proxy {} ({})", proxy.host, proxy.source),
+            format!("TCP connection to proxy {}:{}", proxy.host, proxy.port),
+            "Check the proxy host in the config or HTTP_PROXY/HTTPS_PROXY.",
+            "Make sure the proxy is running and reachable from this machine.",
+        ),
+        None => (
+            host.clone(),
+            port,
+            format!("DNS lookup for {}", host),
+            format!("TCP connection to {}:{}", host, port),
+            "Check your DNS settings or /etc/hosts, or configure a reachable mirror.",
+            "A firewall may block outbound HTTPS; ask your network team or set HTTPS_PROXY.",
+        ),
+    };
+
+    match resolve(&host, port) {
+        Ok(addrs) => {
+            checks.push(Check::new("network.dns", dns_name, Ok(format!("{} address(es)", addrs.len())), dns_fix));
+            checks.push(Check::new("network.tcp", tcp_name, connect(&addrs), tcp_fix));
+        }
+        Err(e) => {
+            checks.push(Check::new("network.dns", dns_name, Err(e), dns_fix));
+            checks.push(Check::skipped("network.tcp", tcp_name, "DNS lookup failed", tcp_fix));
+        }
+    }
+
+    checks.push(Check::new(
+        "network.proxy",
+        "Proxy configuration",
+        check_proxy(),
+        "Fix the proxy URL in the config or HTTP_PROXY/HTTPS_PROXY, or unset it if no proxy is needed.",
+    ));
+
+    checks.push(Check::new(
+        "network.download",
+        "TLS handshake and download",
+        timed_download(&format!("{}/latest/SHASUMS256.txt", dist)),
+        TLS_REMEDIATION,
+    ));
+
+    checks
+}
+
+fn finish(checks: &[Check], as_json: bool, group: &str) -> Result<()> {
+    let failures = checks.iter().filter(|check| check.failed()).count();
+
+    if as_json {
+        let report = json!({
+            "checks": checks.iter().map(Check::to_json).collect::<Vec<_>>(),
+            "failures": failures,
+        });
+        output::print_json(&report)?;
+    } else {
+        checks.iter().for_each(report);
+        say!();
+        if failures == 0 {
+            say!("{}", format!("All {} checks passed", group).success());
+        }
+    }
+
+    let message = format!("{} {} check(s) failed", failures, group);
+    let network_only = checks.iter()
+        .filter(|check| check.failed())
+        .all(|check| check.id.starts_with("network."));
+    match failures {
+        0 => Ok(()),
+        _ if network_only => Err(NodeSparkError::Network(message).into()),
+        _ => Err(anyhow!(message)),
+    }
+}
+
+fn report(check: &Check) {
+    match check.outcome {
+        Outcome::Pass(ref detail) => {
+            say!("{} {} ({})", symbols::ok().success(), check.name, detail);
+        }
+        Outcome::Fail(ref detail) => {
+            say!("{} {}: {}", symbols::fail().error(), check.name, detail);
+            say!("{}{}", symbols::marker_indent(), check.fix.warning());
+        }
+        Outcome::Skip(ref reason) => {
+            say!("{} {} ({})", symbols::skip().muted(), check.name, reason.muted());
+        }
+    }
+}
+
+fn apply_fixes(dirs: &NodeSparkDirs) -> Result<()> {
+    say!("Repairing the node-spark installation...\n");
+    let mut fixed = 0;
+
+    let config_path = dirs.config_dir.join("config.json");
+    let config = match parse_config(dirs) {
+        Ok(config) => config,
+        Err(e) => {
+            verbose::log(&format!("Config is corrupt: {}", e));
+            let backup = config_path.with_extension("json.bak");
+            fs::rename(&config_path, &backup)?;
+            config::save_config(&Config::default())?;
+            say!("{} Rewrote config.json from defaults (backup at {})", symbols::ok().success(), backup.display());
+            fixed += 1;
+            Config::default()
+        }
+    };
+
+    if let Some(ref active) = config.active_version {
+        if dirs.versions_dir.join(active).exists() && check_links(dirs, &config).is_err() {
+            install::create_node_symlinks(active)?;
+            say!("{} Recreated the node, npm and npx links for {}", symbols::ok().success(), active);
+            fixed += 1;
+        }
+    }
+
+    if !crate::alias_path()?.exists() {
+        crate::create_alias()?;
+        fixed += 1;
+    }
+
+    let stale = temp::stale_entries(dirs, temp::STALE_AFTER);
+    if !stale.is_empty() {
+        for leftover in &stale {
+            if leftover.path.is_dir() {
+                fs::remove_dir_all(&leftover.path)?;
+            } else {
+                fs::remove_file(&leftover.path)?;
+            }
+        }
+        say!("{} Cleared {} stale temporary file(s)", symbols::ok().success(), stale.len());
+        fixed += 1;
+    }
+
+    say!("\nApplied {} fix(es)\n", fixed);
+    Ok(())
+}
+
+fn check_bin_dir(dirs: &NodeSparkDirs) -> Result<String> {
+    if !dirs.bin_dir.is_dir() {
+        return Err(anyhow!("{} does not exist", dirs.bin_dir.display()));
+    }
+    if !utils::is_on_path(&dirs.bin_dir) {
+        return Err(anyhow!("{} is not on PATH", dirs.bin_dir.display()));
+    }
+    Ok(dirs.bin_dir.display().to_string())
+}
+
+fn parse_config(dirs: &NodeSparkDirs) -> Result<Config> {
+    let path = dirs.config_dir.join("config.json");
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    let content = fs::read_to_string(&path)?;
+    serde_json::from_str(&content).map_err(|e| anyhow!("{} is not valid: {}", path.display(), e))
+}
+
+fn check_links(dirs: &NodeSparkDirs, config: &Config) -> Result<String> {
+    let active = match config.active_version {
+        Some(ref active) => active,
+        None => return Ok("no active version".to_string()),
+    };
+
+    let version_dir = dirs.versions_dir.join(active);
+    if !version_dir.exists() {
+        return Err(anyhow!("active version {} is not installed", active));
+    }
+
+    let version_bin_dir = utils::version_bin_dir(&version_dir);
+    let mut problems = Vec::new();
+    for name in ["node", "npm", "npx"] {
+        let target = shim::target(&version_bin_dir, name);
+        if target.exists() && !shim::points_to(&dirs.bin_dir.join(name), &target) {
+            problems.push(name);
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(format!("pointing at {}", active))
+    } else {
+        Err(anyhow!("{} missing or not pointing at {}", problems.join(", "), active))
+    }
+}
+
+fn check_writable(dirs: &NodeSparkDirs) -> Result<String> {
+    for dir in [&dirs.config_dir, &dirs.versions_dir, &dirs.bin_dir, &dirs.cache_dir] {
+        let probe = dir.join(".doctor-write-test");
+        fs::write(&probe, b"")
+            .and_then(|_| fs::remove_file(&probe))
+            .map_err(|e| anyhow!("cannot write to {}: {}", dir.display(), e))?;
+    }
+    Ok("config, versions, bin and cache directories".to_string())
+}
+
+fn check_conflicts() -> Result<String> {
+    let home = directories::BaseDirs::new().map(|base| base.home_dir().to_path_buf());
+    let mut found = Vec::new();
+
+    for (name, var, dir) in [("nvm", "NVM_DIR", ".nvm"), ("volta", "VOLTA_HOME", ".volta"), ("fnm", "FNM_DIR", ".fnm")] {
+        let location = env::var_os(var).map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(dir)).filter(|path| path.exists()));
+        if let Some(location) = location {
+            found.push(format!("{} ({})", name, location.display()));
+        }
+    }
+
+    if found.is_empty() {
+        Ok("none found".to_string())
+    } else {
+        Err(anyhow!("found {}", found.join(", ")))
+    }
+}
+
+fn resolve(host: &str, port: u16) -> Result<Vec<SocketAddr>> {
+    let addrs: Vec<SocketAddr> = (host, port).to_socket_addrs()?.collect();
+    if addrs.is_empty() {
+        return Err(anyhow!("no addresses returned"));
+    }
+    Ok(addrs)
+}
+
+fn connect(addrs: &[SocketAddr]) -> Result<String> {
+    let mut last_error = None;
+    for addr in addrs {
+        let started = Instant::now();
+        match TcpStream::connect_timeout(addr, TIMEOUT) {
+            Ok(_) => return Ok(format!("{} in {} ms", addr, started.elapsed().as_millis())),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    Err(anyhow!("{}", last_error.map(|e| e.to_string()).unwrap_or_default()))
+}
+
+fn check_proxy() -> Result<String> {
+    let mut configured = Vec::new();
+    let mut sources: Vec<(&str, String)> = Vec::new();
+
+    if let Some(proxy) = config::load_config()?.proxy {
+        sources.push(("config proxy", proxy));
+    }
+    for var in ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"] {
+        if let Ok(value) = env::var(var) {
+            sources.push((var, value));
+        }
+    }
+
+    for (var, value) in sources {
+        let proxy = reqwest::Url::parse(&value)
+            .map_err(|e| anyhow!("{} is not a valid URL: {}", var, e))?;
+        let host = proxy.host_str().ok_or_else(|| anyhow!("{} has no host", var))?;
+        let port = proxy.port_or_known_default().unwrap_or(80);
+        (host, port).to_socket_addrs()
+            .map_err(|e| anyhow!("cannot resolve proxy host {} from {}: {}", host, var, e))?;
+        configured.push(format!("{}={}", var, value));
+    }
+
+    if configured.is_empty() {
+        Ok("no proxy configured".to_string())
+    } else {
+        Ok(configured.join(", "))
+    }
+}
+
+struct ProxyTarget {
+    source: String,
+    host: String,
+    port: u16,
+}
+
+// The proxy requests to the mirror go through, picked the way the HTTP client picks it
+fn proxy_for(scheme: &str, host: &str) -> Option<ProxyTarget> {
+    if bypasses_proxy(host) {
+        return None;
+    }
+
+    let mut candidates = vec![("config proxy".to_string(), config::load_config().ok().and_then(|config| config.proxy))];
+    let vars: &[&str] = match scheme {
+        "https" => &["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"],
+        _ => &["HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"],
+    };
+    candidates.extend(vars.iter().map(|var| (var.to_string(), env::var(var).ok())));
+
+    let (source, value) = candidates.into_iter()
+        .find_map(|(source, value)| value.filter(|value| !value.is_empty()).map(|value| (source, value)))?;
+    // An unparsable proxy is reported by the proxy check; the direct checks still say something useful
+    let url = reqwest::Url::parse(&value).ok()?;
+    Some(ProxyTarget {
+        source,
+        host: url.host_str()?.to_string(),
+        port: url.port_or_known_default().unwrap_or(80),
+    })
+}
+
+fn bypasses_proxy(host: &str) -> bool {
+    let no_proxy = env::var("NO_PROXY").or_else(|_| env::var("no_proxy")).unwrap_or_default();
+    no_proxy.split(',').map(str::trim).filter(|entry| !entry.is_empty()).any(|entry| {
+        let domain = entry.trim_start_matches('.');
+        entry == "*" || host == domain || host.ends_with(&format!(".{}", domain))
+    })
+}
+
+fn timed_download(probe_url: &str) -> Result<String> {
+    let client = http::builder()?.timeout(TIMEOUT).build()?;
+    let started = Instant::now();
+
+    let resp = client.get(probe_url).send().map_err(describe_request_error)?;
+    if !resp.status().is_success() {
+        return Err(anyhow!("HTTP {} from {}", resp.status(), probe_url));
+    }
+
+    let bytes = resp.bytes()?.len();
+    let elapsed = started.elapsed();
+    let rate = bytes as f64 / 1024.0 / elapsed.as_secs_f64().max(0.001);
+
+    Ok(format!("{} bytes in {} ms, {:.1} KB/s", bytes, elapsed.as_millis(), rate))
+}
+
+fn describe_request_error(error: reqwest::Error) -> anyhow::Error {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        let cause_message = cause.to_string();
+        if !message.contains(&cause_message) {
+            message = format!("{}: {}", message, cause_message);
+        }
+        source = cause.source();
+    }
+
+    if message.to_lowercase().contains("certificate") {
+        anyhow!("{} (the certificate is not trusted; a corporate proxy may be intercepting TLS)", message)
+    } else {
+        anyhow!("{}", message)
+    }
+}
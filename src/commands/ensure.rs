@@ -0,0 +1,53 @@
+use anyhow::{Result, anyhow};
+use crate::commands::{install, r#use};
+use crate::config;
+use crate::error::NodeSparkError;
+use crate::options::theme::Themed;
+use crate::utils::{self, project_version};
+use crate::say;
+
+pub fn execute(version: Option<&str>, project: bool, changed_exit_code: Option<i32>) -> Result<()> {
+    let spec = match (version, project) {
+        (Some(version), _) => version.trim_start_matches('v').to_string(),
+        (None, true) => project_version::find_in_current_dir()?
+            .map(|project| project.spec)
+            .ok_or_else(|| anyhow!("No project version file found in the current directory"))?,
+        (None, false) => return Err(anyhow!("Specify a version or use --project")),
+    };
+
+    let dirs = config::get_dirs()?;
+    let mut changes = Vec::new();
+
+    let installed = utils::installed_versions(&dirs.versions_dir)?;
+    let version = match utils::resolve_spec(&spec, &installed) {
+        Some(version) => version,
+        None => {
+            let version = install::ensure_installed(&spec)?;
+            changes.push("installed");
+            version
+        }
+    };
+
+    let config = config::load_config()?;
+    if config.active_version.as_deref() != Some(version.as_str()) {
+        r#use::execute(Some(&version), false, false)?;
+
+        if config::load_config()?.active_version.as_deref() != Some(version.as_str()) {
+            return Err(anyhow!("Node.js {} was not activated", version));
+        }
+        changes.push("activated");
+    }
+
+    if changes.is_empty() {
+        say!("{}: Node.js {} is installed and active", "unchanged".success(), version);
+        return Ok(());
+    }
+
+    say!("{}: Node.js {} ({})", "changed".warning(), version, changes.join(", "));
+
+    if let Some(code) = changed_exit_code {
+        return Err(NodeSparkError::Exit(code).into());
+    }
+
+    Ok(())
+}
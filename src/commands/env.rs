@@ -0,0 +1,133 @@
+use anyhow::{Result, anyhow};
+use clap::ValueEnum;
+use std::env;
+use std::path::{Path, PathBuf};
+use crate::commands::{daemon, headers, r#use, run};
+use crate::commands::resolve::Resolution;
+use crate::config;
+use crate::error::NodeSparkError;
+use crate::options::output;
+use crate::utils::{self, download};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+    Cmd,
+}
+
+impl Shell {
+    pub fn detect() -> Result<Shell> {
+        if let Ok(path) = env::var("SHELL") {
+            // Only exact names: ksh, csh and tcsh also end in "sh" but cannot eval bash syntax
+            let name = Path::new(&path).file_stem().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+            return match name.as_str() {
+                "bash" | "sh" => Ok(Shell::Bash),
+                "zsh" => Ok(Shell::Zsh),
+                "fish" => Ok(Shell::Fish),
+                _ => Err(anyhow!("Unsupported shell {}; pass --shell bash, zsh, fish, powershell or cmd", path)),
+            };
+        }
+
+        Ok(if cfg!(target_os = "windows") {
+            if env::var_os("PSModulePath").is_some() {
+                Shell::Powershell
+            } else {
+                Shell::Cmd
+            }
+        } else {
+            Shell::Bash
+        })
+    }
+
+    pub fn quote(self, value: &str) -> String {
+        match self {
+            Shell::Bash | Shell::Zsh => format!("'{}'", value.replace('\'', "'\\''")),
+            Shell::Fish => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'")),
+            Shell::Powershell => format!("'{}'", value.replace('\'', "''")),
+            Shell::Cmd => format!("\"{}\"", value),
+        }
+    }
+
+    pub fn set_var(self, key: &str, value: &str) -> String {
+        match self {
+            Shell::Bash | Shell::Zsh => format!("export {}={}", key, self.quote(value)),
+            Shell::Fish => format!("set -gx {} {}", key, self.quote(value)),
+            Shell::Powershell => format!("$env:{} = {}", key, self.quote(value)),
+            Shell::Cmd => format!("SET \"{}={}\"", key, value),
+        }
+    }
+
+    pub fn set_path(self, paths: &[PathBuf]) -> Result<String> {
+        if self == Shell::Fish {
+            let entries: Vec<String> = paths.iter()
+                .map(|path| self.quote(&path.to_string_lossy()))
+                .collect();
+            return Ok(format!("set -gx PATH {}", entries.join(" ")));
+        }
+
+        let joined = env::join_paths(paths)?;
+        Ok(self.set_var("PATH", &joined.to_string_lossy()))
+    }
+}
+
+pub fn execute(version: Option<&str>, shell: Option<Shell>, no_install: bool) -> Result<()> {
+    // Our stdout is eval'd, so progress from an auto_install must not end up in it
+    output::reserve_stdout();
+    let shell = shell.map_or_else(Shell::detect, Ok)?;
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+
+    let version = match version {
+        Some(spec) if download::is_alias(spec) => r#use::resolve_channel(&dirs, &spec.to_lowercase())?,
+        Some(spec) => utils::resolve_spec(spec, &utils::installed_versions(&dirs.versions_dir)?)
+            .ok_or_else(|| NodeSparkError::not_installed(spec))?,
+        None if no_install => hook_version()?,
+        None => run::resolve_project_version()?,
+    };
+
+    for line in exports(shell, &version, &config)? {
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+// The init hooks run `env --no-install` on every directory change, so ask the resolver daemon first
+fn hook_version() -> Result<String> {
+    match daemon::query(&env::current_dir()?) {
+        Some(Resolution::Installed { version, .. }) => Ok(version),
+        Some(Resolution::Missing { spec, source }) => Err(anyhow!(
+            "Node.js {} required by {} is not installed. Use 'node-spark install {}' first.", spec, source, spec
+        )),
+        Some(Resolution::Nothing) | None => run::resolve_project_version_with(false),
+    }
+}
+
+pub fn exports(shell: Shell, version: &str, config: &config::Config) -> Result<Vec<String>> {
+    let dirs = config::get_dirs()?;
+    let bin_dir = utils::version_bin_dir(&dirs.versions_dir.join(version));
+
+    let mut paths = vec![bin_dir];
+    if let Some(existing) = env::var_os("PATH") {
+        paths.extend(env::split_paths(&existing).filter(|path| !path.starts_with(&dirs.versions_dir)));
+    }
+
+    let mut lines = vec![
+        shell.set_path(&paths)?,
+        shell.set_var("NODE_SPARK_VERSION", version),
+    ];
+
+    if let Some(nodedir) = headers::nodedir(&dirs, version) {
+        lines.push(shell.set_var("npm_config_nodedir", &nodedir.to_string_lossy()));
+    }
+    if let Some(version_env) = config.version_env.get(version) {
+        for (key, value) in version_env {
+            lines.push(shell.set_var(key, value));
+        }
+    }
+
+    Ok(lines)
+}
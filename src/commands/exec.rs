@@ -0,0 +1,65 @@
+use anyhow::{Result, anyhow};
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+use crate::error::NodeSparkError;
+use crate::commands::{headers, r#use};
+use crate::config;
+use crate::options::verbose;
+use crate::utils::{self, download, env_file};
+
+pub fn execute(version: &str, env_files: &[PathBuf], command: &[String]) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let actual_version = if download::is_alias(version) {
+        r#use::resolve_channel(&dirs, &version.to_lowercase())?
+    } else {
+        utils::resolve_spec(version, &utils::installed_versions(&dirs.versions_dir)?)
+            .ok_or_else(|| NodeSparkError::not_installed(version))?
+    };
+
+    if !dirs.versions_dir.join(&actual_version).exists() {
+        return Err(NodeSparkError::VersionNotFound(format!("Node.js {} is not installed. Use 'node-spark install {}' first.",
+                            actual_version, actual_version)).into());
+    }
+
+    run_under(&actual_version, env_files, command)
+}
+
+pub fn run_under(version: &str, env_files: &[PathBuf], command: &[String]) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let version_dir = dirs.versions_dir.join(version);
+
+    let (program, args) = command.split_first()
+        .ok_or_else(|| anyhow!("No command given. Usage: node-spark exec <version> -- <command> [args...]"))?;
+
+    let mut child = Command::new(program);
+    child.args(args);
+
+    config::record_usage(version)?;
+    let config = config::load_config()?;
+    if let Some(nodedir) = headers::nodedir(&dirs, version) {
+        child.env("npm_config_nodedir", nodedir);
+    }
+    if let Some(version_env) = config.version_env.get(version) {
+        child.envs(version_env);
+    }
+
+    child.envs(env_file::load_all(env_files)?);
+
+    let bin_dir = utils::version_bin_dir(&version_dir);
+    let mut paths = vec![bin_dir];
+    if let Some(existing) = env::var_os("PATH") {
+        paths.extend(env::split_paths(&existing));
+    }
+    child.env("PATH", env::join_paths(paths)?);
+
+    verbose::log(&format!("Running {} under Node.js {}", program, version));
+    let status = child.status()
+        .map_err(|e| anyhow!("Failed to run {}: {}", program, e))?;
+
+    if !status.success() {
+        return Err(NodeSparkError::Exit(status.code().unwrap_or(1)).into());
+    }
+
+    Ok(())
+}
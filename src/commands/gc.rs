@@ -0,0 +1,101 @@
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::config;
+use crate::options::verbose;
+use crate::utils::{self, disk, shim, temp};
+use crate::options::theme::Themed;
+use crate::say;
+
+struct Garbage {
+    path: PathBuf,
+    reason: &'static str,
+    size: u64,
+}
+
+pub fn execute(dry_run: bool) -> Result<()> {
+    verbose::log("Executing gc command");
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+    let installed = utils::installed_versions(&dirs.versions_dir)?;
+    let is_installed = |name: &str| {
+        installed.iter().any(|version| version == name) || config.linked_versions.contains_key(name)
+    };
+
+    let mut garbage = Vec::new();
+
+    for entry in read_dir(&dirs.receipts_dir) {
+        let path = entry.path();
+        let version = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+        if path.extension().is_some_and(|ext| ext == "json") && !is_installed(&version) {
+            garbage.push(Garbage { size: disk_size(&path), path, reason: "receipt without installed version" });
+        }
+    }
+
+    for entry in read_dir(&dirs.headers_dir) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with(".staging-") && !is_installed(&name) {
+            garbage.push(Garbage { size: disk_size(&entry.path()), path: entry.path(), reason: "headers for removed version" });
+        }
+    }
+
+    for leftover in temp::stale_entries(&dirs, temp::STALE_AFTER) {
+        garbage.push(Garbage { size: disk_size(&leftover.path), path: leftover.path, reason: leftover.kind.reason() });
+    }
+
+    for entry in read_dir(&dirs.bin_dir) {
+        let path = entry.path();
+        let dangling = path.symlink_metadata().is_ok_and(|m| m.file_type().is_symlink()) && !path.exists();
+        if dangling {
+            garbage.push(Garbage { size: 0, path, reason: "dangling shim" });
+        }
+    }
+
+    if garbage.is_empty() {
+        say!("Nothing to collect");
+        return Ok(());
+    }
+
+    let mut reclaimed = 0;
+    for item in &garbage {
+        say!("  {} ({}, {})", item.path.display(), item.reason.muted(), disk::format_size(item.size));
+        if !dry_run {
+            remove(item)?;
+        }
+        reclaimed += item.size;
+    }
+
+    if dry_run {
+        say!("Would remove {} item(s), reclaiming {}", garbage.len(), disk::format_size(reclaimed).success());
+    } else {
+        say!("Removed {} item(s), reclaimed {}", garbage.len(), disk::format_size(reclaimed).success());
+    }
+
+    Ok(())
+}
+
+fn read_dir(dir: &Path) -> Vec<fs::DirEntry> {
+    fs::read_dir(dir)
+        .map(|entries| entries.flatten().collect())
+        .unwrap_or_default()
+}
+
+fn disk_size(path: &Path) -> u64 {
+    match path.symlink_metadata() {
+        Ok(metadata) if metadata.is_dir() => disk::dir_size(path),
+        Ok(metadata) => metadata.len(),
+        Err(_) => 0,
+    }
+}
+
+fn remove(item: &Garbage) -> Result<()> {
+    let metadata = item.path.symlink_metadata()?;
+    if metadata.file_type().is_symlink() {
+        shim::remove(&item.path)?;
+    } else if metadata.is_dir() {
+        fs::remove_dir_all(&item.path)?;
+    } else {
+        fs::remove_file(&item.path)?;
+    }
+    Ok(())
+}
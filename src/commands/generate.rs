@@ -0,0 +1,118 @@
+use anyhow::{Result, anyhow};
+use clap::ValueEnum;
+use serde_json::{json, Value};
+use std::env;
+use std::fs;
+use std::path::Path;
+use crate::commands::run;
+use crate::config;
+use crate::options::theme::Themed;
+use crate::utils;
+use crate::say;
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Editor {
+    Vscode,
+    Jetbrains,
+}
+
+pub fn execute(editor: Editor) -> Result<()> {
+    let version = run::resolve_project_version()?;
+    let dirs = config::get_dirs()?;
+    let bin_dir = utils::version_bin_dir(&dirs.versions_dir.join(&version));
+    let node_path = if cfg!(target_os = "windows") {
+        bin_dir.join("node.exe")
+    } else {
+        bin_dir.join("node")
+    };
+
+    let project_dir = env::current_dir()?;
+    match editor {
+        Editor::Vscode => generate_vscode(&project_dir, &node_path, &bin_dir)?,
+        Editor::Jetbrains => generate_jetbrains(&project_dir, &node_path)?,
+    }
+
+    say!("Editor now uses Node.js {} ({})", version.success(), node_path.display());
+    Ok(())
+}
+
+fn generate_vscode(project_dir: &Path, node_path: &Path, bin_dir: &Path) -> Result<()> {
+    let settings_dir = project_dir.join(".vscode");
+    let settings_path = settings_dir.join("settings.json");
+    fs::create_dir_all(&settings_dir)?;
+
+    let mut settings: Value = if settings_path.exists() {
+        let content = fs::read_to_string(&settings_path)?;
+        serde_json::from_str(&content).map_err(|_| anyhow!(
+            "{} could not be parsed (comments or trailing commas?); remove them and re-run",
+            settings_path.display()
+        ))?
+    } else {
+        json!({})
+    };
+
+    let settings_map = settings.as_object_mut()
+        .ok_or_else(|| anyhow!("{} is not a JSON object", settings_path.display()))?;
+
+    let node = node_path.to_string_lossy().to_string();
+    let separator = if cfg!(target_os = "windows") { ";" } else { ":" };
+    let path_value = format!("{}{}${{env:PATH}}", bin_dir.display(), separator);
+
+    settings_map.insert("eslint.runtime".to_string(), json!(node));
+    settings_map.insert("debug.javascript.defaultRuntimeExecutable".to_string(), json!({ "pwa-node": node }));
+    let platform = if cfg!(target_os = "windows") {
+        "windows"
+    } else if cfg!(target_os = "macos") {
+        "osx"
+    } else {
+        "linux"
+    };
+    settings_map.insert(format!("terminal.integrated.env.{}", platform), json!({ "PATH": path_value }));
+
+    fs::write(&settings_path, serde_json::to_string_pretty(&settings)? + "\n")?;
+    say!("Updated {}", settings_path.display());
+    Ok(())
+}
+
+fn generate_jetbrains(project_dir: &Path, node_path: &Path) -> Result<()> {
+    let idea_dir = project_dir.join(".idea");
+    let workspace_path = idea_dir.join("workspace.xml");
+    let property = format!(
+        "<property name=\"nodejs_interpreter_path\" value=\"{}\" />",
+        xml_escape(&node_path.to_string_lossy())
+    );
+
+    if !workspace_path.exists() {
+        fs::create_dir_all(&idea_dir)?;
+        fs::write(&workspace_path, format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<project version=\"4\">\n  <component name=\"PropertiesComponent\">\n    {}\n  </component>\n</project>\n",
+            property
+        ))?;
+        say!("Created {}", workspace_path.display());
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(&workspace_path)?;
+    let updated = if let Some(start) = content.find("<property name=\"nodejs_interpreter_path\"") {
+        let end = content[start..].find("/>")
+            .map(|offset| start + offset + 2)
+            .ok_or_else(|| anyhow!("Malformed nodejs_interpreter_path in {}", workspace_path.display()))?;
+        format!("{}{}{}", &content[..start], property, &content[end..])
+    } else if let Some(start) = content.find("<component name=\"PropertiesComponent\">") {
+        let insert_at = start + "<component name=\"PropertiesComponent\">".len();
+        format!("{}\n    {}{}", &content[..insert_at], property, &content[insert_at..])
+    } else {
+        return Err(anyhow!(
+            "Could not find a PropertiesComponent in {}. Set the Node.js interpreter to {} in Settings | Languages & Frameworks | Node.js",
+            workspace_path.display(), node_path.display()
+        ));
+    };
+
+    fs::write(&workspace_path, updated)?;
+    say!("Updated {}", workspace_path.display());
+    Ok(())
+}
+
+fn xml_escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;").replace('>', "&gt;")
+}
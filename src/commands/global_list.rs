@@ -1,11 +1,12 @@
 use anyhow::Result;
-use colored::Colorize;
 use std::process::Command;
 use crate::options::verbose;
+use crate::options::theme::Themed;
+use crate::say;
 
 pub fn execute() -> Result<()> {
     verbose::log("Executing global-list command");
-    println!("Listing globally installed npm packages...");
+    say!("Listing globally installed npm packages...");
     
     let npm_cmd = if cfg!(target_os = "windows") {
         "npm.cmd"
@@ -33,12 +34,12 @@ pub fn execute() -> Result<()> {
             if parts.len() == 2 {
                 let name = parts[0].trim();
                 let version = parts[1].trim();
-                println!("{} {}", name.green(), format!("@{}", version).yellow());
+                say!("{} {}", name.success(), format!("@{}", version).info());
             } else {
-                println!("{}", line);
+                say!("{}", line);
             }
         } else {
-            println!("{}", line);
+            say!("{}", line);
         }
     }
     
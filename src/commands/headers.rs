@@ -0,0 +1,62 @@
+use anyhow::{Result, anyhow};
+use std::fs;
+use std::path::PathBuf;
+use crate::commands::install;
+use crate::config::{self, NodeSparkDirs};
+use crate::utils::{self, download, extract, interrupt};
+use crate::options::theme::Themed;
+use crate::say;
+
+pub fn execute(version: &str) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let actual_version = utils::parse_version(version)?;
+
+    if nodedir(&dirs, &actual_version).is_some() {
+        say!("Headers for Node.js {} are already installed", actual_version);
+        return Ok(());
+    }
+
+    install_headers(&dirs, &actual_version)?;
+    say!("Installed headers for Node.js {} into {}",
+        actual_version.success(), dirs.headers_dir.join(&actual_version).display());
+
+    let config = config::load_config()?;
+    if config.active_version.as_deref() == Some(actual_version.as_str()) {
+        install::create_node_symlinks(&actual_version)?;
+    }
+
+    Ok(())
+}
+
+pub fn install_headers(dirs: &NodeSparkDirs, version: &str) -> Result<()> {
+    let temp_dir = dirs.config_dir.join("temp");
+    fs::create_dir_all(&temp_dir)?;
+
+    let url = utils::get_headers_url(version);
+    let download_path = temp_dir.join(format!("node-v{}-headers.tar.gz", version));
+    let download_guard = interrupt::guard(&download_path);
+    download::download_file(&url, &download_path, None)?;
+
+    let staging_dir = dirs.headers_dir.join(format!(".staging-{}", version));
+    let staging_guard = interrupt::guard(&staging_dir);
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir)?;
+    }
+    fs::create_dir_all(&staging_dir)?;
+    extract::extract_archive(&download_path, &staging_dir, None)?;
+
+    let root = staging_dir.join(format!("node-v{}", version));
+    if !root.join("include").join("node").is_dir() {
+        return Err(anyhow!("Headers archive for Node.js {} has an unexpected layout", version));
+    }
+    fs::rename(root, dirs.headers_dir.join(version))?;
+
+    drop(staging_guard);
+    drop(download_guard);
+    Ok(())
+}
+
+pub fn nodedir(dirs: &NodeSparkDirs, version: &str) -> Option<PathBuf> {
+    let dir = dirs.headers_dir.join(version);
+    dir.join("include").join("node").is_dir().then_some(dir)
+}
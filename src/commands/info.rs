@@ -0,0 +1,65 @@
+use anyhow::Result;
+use serde_json::json;
+use crate::config;
+use crate::error::NodeSparkError;
+use crate::options::{output, verbose};
+use crate::options::theme::Themed;
+use crate::utils::{download, schedule};
+use crate::say;
+
+pub fn execute(spec: &str, as_json: bool) -> Result<()> {
+    verbose::log("Executing info command");
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+    let index = download::index_for(spec)?;
+
+    let version = download::resolve_remote(spec, &index)
+        .ok_or_else(|| NodeSparkError::VersionNotFound(format!("No Node.js release matches {}", spec)))?;
+    let remote = index.iter()
+        .find(|remote| remote.version == version)
+        .ok_or_else(|| NodeSparkError::VersionNotFound(format!("Node.js {} is not in the release index", version)))?;
+
+    let version_dir = dirs.versions_dir.join(&version);
+    let install_path = config.linked_versions.get(&version).cloned()
+        .or_else(|| version_dir.exists().then(|| version_dir.clone()));
+    let phase = schedule::load().and_then(|schedule| schedule.phase(&version));
+
+    if as_json {
+        return output::print_json(&json!({
+            "version": version,
+            "date": remote.date,
+            "lts": remote.lts,
+            "status": phase.as_ref().map(|phase| phase.name()),
+            "security": remote.security,
+            "npm": remote.npm,
+            "v8": remote.v8,
+            "openssl": remote.openssl,
+            "modules": remote.modules,
+            "installed": install_path.is_some(),
+            "active": config.active_version.as_ref() == Some(&version),
+            "path": install_path,
+        }));
+    }
+
+    let unknown = || "unknown".muted().to_string();
+    let field = |value: &Option<String>| value.clone().unwrap_or_else(unknown);
+
+    say!("Node.js {}", version.success());
+    say!("  Released:   {}", remote.date);
+    say!("  LTS:        {}", remote.lts.as_deref().map(|lts| lts.info().to_string()).unwrap_or_else(|| "no".muted().to_string()));
+    say!("  Status:     {}", phase.map(|phase| phase.badge()).unwrap_or_else(unknown));
+    say!("  Security:   {}", if remote.security { "yes, this is a security release".error().to_string() } else { "no".to_string() });
+    say!("  npm:        {}", field(&remote.npm));
+    say!("  V8:         {}", field(&remote.v8));
+    say!("  OpenSSL:    {}", field(&remote.openssl));
+    say!("  Modules:    {}", remote.modules.as_ref().map(|modules| format!("{} (NODE_MODULE_VERSION)", modules)).unwrap_or_else(unknown));
+
+    match install_path {
+        Some(path) => {
+            let current = if config.active_version.as_ref() == Some(&version) { format!(" {}", "(current)".current()) } else { String::new() };
+            say!("  Installed:  {}{}", path.display(), current);
+        }
+        None => say!("  Installed:  {} (run 'node-spark install {}')", "no".muted(), version),
+    }
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anyhow::{Result, anyhow};
+use std::env;
+use crate::commands::env::Shell;
+use crate::config;
+
+// Exported by every hook so `status` can tell whether automatic switching is loaded
+pub const HOOK_VAR: &str = "NODE_SPARK_SHELL_HOOK";
+
+pub fn execute(shell: Shell) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let exe = env::current_exe()?.to_string_lossy().to_string();
+    let bin_dir = dirs.bin_dir.to_string_lossy().to_string();
+
+    let script = match shell {
+        Shell::Bash => bash_hook(&exe, &bin_dir),
+        Shell::Zsh => zsh_hook(&exe, &bin_dir),
+        Shell::Fish => fish_hook(&exe, &bin_dir),
+        Shell::Powershell => powershell_hook(&exe, &bin_dir),
+        Shell::Cmd => return Err(anyhow!("cmd has no prompt hook for automatic switching; use 'node-spark env --shell cmd' instead")),
+    };
+
+    print!("{}", script);
+    Ok(())
+}
+
+fn bash_hook(exe: &str, bin_dir: &str) -> String {
+    format!(r#"export PATH={bin_dir}:"$PATH"
+export NODE_SPARK_SHELL_HOOK=bash
+_node_spark_hook() {{
+  if [ "$_NODE_SPARK_PWD" != "$PWD" ]; then
+    _NODE_SPARK_PWD="$PWD"
+    eval "$({exe} env --shell bash --no-install)"
+  fi
+}}
+if [[ ";${{PROMPT_COMMAND[*]:-}};" != *";_node_spark_hook;"* ]]; then
+  PROMPT_COMMAND="_node_spark_hook${{PROMPT_COMMAND:+;$PROMPT_COMMAND}}"
+fi
+_node_spark_hook
+"#,
+        bin_dir = Shell::Bash.quote(bin_dir),
+        exe = Shell::Bash.quote(exe),
+    )
+}
+
+fn zsh_hook(exe: &str, bin_dir: &str) -> String {
+    format!(r#"export PATH={bin_dir}:"$PATH"
+export NODE_SPARK_SHELL_HOOK=zsh
+_node_spark_hook() {{
+  eval "$({exe} env --shell zsh --no-install)"
+}}
+autoload -U add-zsh-hook
+add-zsh-hook chpwd _node_spark_hook
+_node_spark_hook
+"#,
+        bin_dir = Shell::Zsh.quote(bin_dir),
+        exe = Shell::Zsh.quote(exe),
+    )
+}
+
+fn fish_hook(exe: &str, bin_dir: &str) -> String {
+    format!(r#"set -gx PATH {bin_dir} $PATH
+set -gx NODE_SPARK_SHELL_HOOK fish
+function _node_spark_hook --on-variable PWD
+  {exe} env --shell fish --no-install | source
+end
+_node_spark_hook
+"#,
+        bin_dir = Shell::Fish.quote(bin_dir),
+        exe = Shell::Fish.quote(exe),
+    )
+}
+
+fn powershell_hook(exe: &str, bin_dir: &str) -> String {
+    format!(r#"$env:PATH = {bin_dir} + [IO.Path]::PathSeparator + $env:PATH
+$env:NODE_SPARK_SHELL_HOOK = "powershell"
+function global:_node_spark_hook {{
+  if ($global:_NodeSparkPwd -ne $PWD.Path) {{
+    $global:_NodeSparkPwd = $PWD.Path
+    & {exe} env --shell powershell --no-install | Out-String | Invoke-Expression
+  }}
+}}
+if (-not $global:_NodeSparkPrompt) {{
+  $global:_NodeSparkPrompt = $function:prompt
+  function global:prompt {{ _node_spark_hook; & $global:_NodeSparkPrompt }}
+}}
+_node_spark_hook
+"#,
+        bin_dir = Shell::Powershell.quote(bin_dir),
+        exe = Shell::Powershell.quote(exe),
+    )
+}
@@ -1,113 +1,705 @@
 use anyhow::{Result, anyhow};
-use colored::Colorize;
+use std::collections::BTreeMap;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+use indicatif::MultiProgress;
+use serde_json::json;
+use crate::error::NodeSparkError;
+use crate::commands::{audit_versions, headers, r#use, remove, setup};
 use crate::config;
-use crate::utils::{self, download, extract};
+use crate::options::{events, output, verbose};
+use crate::options::picker::{self, PickerItem};
+use crate::utils::channel::Channel;
+use crate::utils::platform::{Flavor, Platform};
+use crate::utils::{self, VersionSpec, archive_cache, disk, download, extract, interrupt, permissions, project_version, receipt, shim, signature};
+use crate::options::theme::Themed;
+use crate::say;
 
-pub fn execute(version: &str) -> Result<()> {
-    let dirs = config::get_dirs()?;
+const MAX_PARALLEL_INSTALLS: usize = 4;
+
+#[derive(Default)]
+pub struct InstallOptions {
+    pub skip_checksum: bool,
+    pub verify_signatures: bool,
+    pub offline: bool,
+    pub force: bool,
+    pub flavor: Option<Flavor>,
+    pub arch: Option<&'static str>,
+    pub file: Option<PathBuf>,
+    pub progress: Option<MultiProgress>,
+}
+
+impl InstallOptions {
+    pub fn platform(&self) -> Platform {
+        Platform::host().with_arch(self.arch).with_flavor(self.flavor)
+    }
     
-    let actual_version = if version == "latest" || version == "lts" {
-        println!("Fetching {} Node.js version...", version);
-        let available_versions = download::get_available_versions()?;
-        
-        if available_versions.is_empty() {
-            return Err(anyhow!("No available Node.js versions found"));
-        }
-        
-        if version == "latest" {
-            available_versions.first().unwrap().clone()
+    pub fn platform_for(&self, version: &str) -> Platform {
+        let platform = self.platform();
+        if self.needs_rosetta(&platform, version) {
+            platform.with_arch(Some("x64"))
         } else {
-            available_versions.first().unwrap().clone()
+            platform
         }
-    } else {
+    }
+    
+    // Node.js only ships darwin-arm64 builds since 16.0.0; older ones run as x64 under Rosetta
+    fn needs_rosetta(&self, platform: &Platform, version: &str) -> bool {
+        self.arch.is_none()
+            && platform.os == "darwin"
+            && platform.arch == "arm64"
+            && utils::major_version(version).is_some_and(|major| major < 16)
+    }
+}
+
+pub fn execute(version: Option<&str>, from_dir: Option<&Path>, with_headers: bool, options: &InstallOptions) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    
+    let (version, from_project) = match (version, options.file.as_deref()) {
+        (Some(version), _) => (version.to_string(), false),
+        (None, Some(file)) => (archive_name(file)?.0, false),
+        (None, None) => {
+            let project = project_version::find_in_current_dir()?
+                .ok_or_else(|| anyhow!("No version given and no .nvmrc, .node-version or package.json engines.node found in this directory or its parents"))?;
+            say!("Using Node.js {} from {}", project.spec, project.source.display());
+            (project.spec, true)
+        }
+    };
+    let version = version.as_str();
+    
+    let actual_version = if from_dir.is_some() || options.file.is_some() {
         utils::parse_version(version)?
+    } else {
+        resolve_requested(&dirs, version, from_project, options)?
     };
+    if let Some(ref file) = options.file {
+        check_file(file, &actual_version, &options.platform_for(&actual_version))?;
+    }
     
-    println!("Installing Node.js {}", actual_version.green());
+    say!("Installing Node.js {}", actual_version.success());
     
+    restore_replaced(&dirs, &actual_version)?;
     let version_dir = dirs.versions_dir.join(&actual_version);
-    if version_dir.exists() {
-        println!("Node.js {} is already installed", actual_version);
+    if version_dir.exists() && !options.force {
+        say!("Node.js {} is already installed", actual_version);
+        if with_headers {
+            headers::execute(&actual_version)?;
+        }
         return Ok(());
     }
     
-    let temp_dir = dirs.config_dir.join("temp");
-    fs::create_dir_all(&temp_dir)?;
-    
-    let download_url = utils::get_download_url(&actual_version);
-    let extension = if cfg!(target_os = "windows") { "zip" } else { "tar.gz" };
-    let download_path = temp_dir.join(format!("node-v{}.{}", actual_version, extension));
-    
-    download::download_file(&download_url, &download_path)?;
-    
-    println!("Extracting Node.js {}...", actual_version);
-    fs::create_dir_all(&version_dir)?;
-    extract::extract_archive(&download_path, &version_dir)?;
+    let replaced = if version_dir.exists() { Some(set_aside(&dirs, &actual_version)?) } else { None };
+    let result = match from_dir {
+        Some(dir) => adopt_version(&actual_version, dir),
+        None => install_version(&actual_version, options),
+    };
+    if let Some((aside, _restore_guard)) = replaced {
+        finish_replacement(&dirs, &actual_version, &aside)?;
+    }
+    result?;
     
-    fs::remove_file(download_path)?;
+    say!("Successfully installed Node.js {}", actual_version.success());
+    record_arch(&actual_version, &options.platform_for(&actual_version))?;
+    audit_versions::warn_if_vulnerable(&actual_version);
+    setup::warn_if_not_on_path(&dirs);
+    remove::remove_superseded(&actual_version)?;
     
-    println!("Successfully installed Node.js {}", actual_version.green());
+    if with_headers && headers::nodedir(&dirs, &actual_version).is_none() {
+        say!("Downloading headers for Node.js {}...", actual_version);
+        headers::install_headers(&dirs, &actual_version)?;
+    }
     
     let mut config = config::load_config()?;
     if config.active_version.is_none() {
-        println!("Setting Node.js {} as the default version", actual_version);
+        say!("Setting Node.js {} as the default version", actual_version);
         config.active_version = Some(actual_version.clone());
         config::save_config(&config)?;
         
         create_node_symlinks(&actual_version)?;
+    } else if config.tracked_channel.is_some() && !options.offline {
+        r#use::refresh_tracked_channel()?;
     }
     
     Ok(())
 }
 
-pub fn create_node_symlinks(version: &str) -> Result<()> {
+pub fn pick_version() -> Result<Option<String>> {
     let dirs = config::get_dirs()?;
-    let version_bin_dir = dirs.versions_dir.join(version).join("bin");
+    let index = download::get_remote_index()?;
+    
+    let items: Vec<PickerItem> = index.iter().map(|remote| {
+        let mut notes = Vec::new();
+        if let Some(ref codename) = remote.lts {
+            notes.push(format!("LTS {}", codename));
+        }
+        if dirs.versions_dir.join(&remote.version).exists() {
+            notes.push("installed".to_string());
+        }
+        PickerItem {
+            label: remote.version.clone(),
+            note: (!notes.is_empty()).then(|| format!("({})", notes.join(", "))),
+            locked: false,
+        }
+    }).collect();
     
-    let node_path = version_bin_dir.join("node");
-    let npm_path = version_bin_dir.join("npm");
-    let npx_path = version_bin_dir.join("npx");
+    let picked = picker::select("Select a Node.js version to install", &items)?;
+    Ok(picked.map(|i| index[i].version.clone()))
+}
+
+pub fn execute_many(versions: &[String], with_headers: bool, options: &InstallOptions) -> Result<()> {
+    let dirs = config::get_dirs()?;
     
-    let node_link = dirs.bin_dir.join("node");
-    let npm_link = dirs.bin_dir.join("npm");
-    let npx_link = dirs.bin_dir.join("npx");
+    let mut pending = Vec::new();
+    for spec in versions {
+        let version = resolve_requested(&dirs, spec, false, options)?;
+        restore_replaced(&dirs, &version)?;
+        if dirs.versions_dir.join(&version).exists() && !options.force {
+            say!("Node.js {} is already installed", version);
+        } else if !pending.contains(&version) {
+            pending.push(version);
+        }
+    }
     
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs as unix_fs;
-        if node_link.exists() {
-            fs::remove_file(&node_link)?;
+    let mut replaced = Vec::new();
+    for version in &pending {
+        if dirs.versions_dir.join(version).exists() {
+            replaced.push((version, set_aside(&dirs, version)?));
         }
-        if npm_link.exists() {
-            fs::remove_file(&npm_link)?;
+    }
+    
+    if !pending.is_empty() {
+        say!("Installing Node.js {} ({} at a time)", pending.join(", ").success(), MAX_PARALLEL_INSTALLS.min(pending.len()));
+    }
+    
+    let progress = MultiProgress::new();
+    let parallel_options = InstallOptions {
+        skip_checksum: options.skip_checksum,
+        verify_signatures: options.verify_signatures,
+        offline: options.offline,
+        force: options.force,
+        flavor: options.flavor,
+        arch: options.arch,
+        file: None,
+        progress: Some(progress.clone()),
+    };
+    output::set_progress(Some(progress));
+    let queue = Mutex::new(pending.iter());
+    let failures = Mutex::new(Vec::new());
+    
+    thread::scope(|scope| {
+        for _ in 0..MAX_PARALLEL_INSTALLS.min(pending.len()) {
+            scope.spawn(|| loop {
+                let next = queue.lock().unwrap().next();
+                let Some(version) = next else { break };
+                if let Err(e) = install_version(version, &parallel_options) {
+                    failures.lock().unwrap().push(format!("{}: {}", version, e));
+                }
+            });
+        }
+    });
+    output::set_progress(None);
+    
+    let failures = failures.into_inner().unwrap();
+    for (version, (aside, _)) in &replaced {
+        finish_replacement(&dirs, version, aside)?;
+    }
+    drop(replaced);
+    for version in pending.iter().filter(|version| dirs.versions_dir.join(version).exists()) {
+        say!("Successfully installed Node.js {}", version.success());
+        record_arch(version, &options.platform_for(version))?;
+        audit_versions::warn_if_vulnerable(version);
+        if with_headers && headers::nodedir(&dirs, version).is_none() {
+            headers::install_headers(&dirs, version)?;
+        }
+    }
+    // Only once every install is done, so the policy sees the whole batch
+    for version in &pending {
+        if dirs.versions_dir.join(version).exists() {
+            remove::remove_superseded(version)?;
+        }
+    }
+    
+    let mut config = config::load_config()?;
+    if config.active_version.is_none() {
+        if let Some(first) = pending.iter().find(|version| dirs.versions_dir.join(version).exists()) {
+            say!("Setting Node.js {} as the default version", first);
+            config.active_version = Some(first.clone());
+            config::save_config(&config)?;
+            create_node_symlinks(first)?;
         }
-        if npx_link.exists() {
-            fs::remove_file(&npx_link)?;
+    } else if config.tracked_channel.is_some() && !options.offline {
+        r#use::refresh_tracked_channel()?;
+    }
+    setup::warn_if_not_on_path(&dirs);
+    
+    if !failures.is_empty() {
+        return Err(anyhow!("{} of {} install(s) failed:\n  {}", failures.len(), pending.len(), failures.join("\n  ")));
+    }
+    Ok(())
+}
+
+fn record_arch(version: &str, platform: &Platform) -> Result<()> {
+    let mut config = config::load_config()?;
+    let changed = if platform.arch == utils::host_arch() {
+        config.version_arch.remove(version).is_some()
+    } else {
+        config.version_arch.insert(version.to_string(), platform.arch.to_string()).as_deref() != Some(platform.arch)
+    };
+    if changed {
+        config::save_config(&config)?;
+    }
+    Ok(())
+}
+
+fn replaced_path(dirs: &config::NodeSparkDirs, version: &str) -> PathBuf {
+    dirs.versions_dir.join(format!(".staging-replaced-{}", version))
+}
+
+// The guard renames the previous install back if the reinstall is interrupted before finish_replacement
+fn set_aside(dirs: &config::NodeSparkDirs, version: &str) -> Result<(PathBuf, interrupt::RestoreGuard)> {
+    if let Some(source) = config::load_config()?.linked_versions.get(version) {
+        return Err(anyhow!("Node.js {} is linked from {}; it cannot be reinstalled", version, source.display()));
+    }
+    
+    say!("Replacing the existing Node.js {} install", version);
+    let aside = replaced_path(dirs, version);
+    if aside.exists() {
+        fs::remove_dir_all(&aside)?;
+    }
+    let version_dir = dirs.versions_dir.join(version);
+    let restore_guard = interrupt::restore_on_interrupt(&aside, &version_dir);
+    fs::rename(&version_dir, &aside)?;
+    Ok((aside, restore_guard))
+}
+
+// A reinstall killed before it could restore the previous install leaves it set aside; put it back
+fn restore_replaced(dirs: &config::NodeSparkDirs, version: &str) -> Result<()> {
+    let aside = replaced_path(dirs, version);
+    let version_dir = dirs.versions_dir.join(version);
+    if aside.is_dir() && !version_dir.exists() {
+        say!("Restoring Node.js {} from an interrupted reinstall", version);
+        fs::rename(&aside, &version_dir)?;
+    }
+    Ok(())
+}
+
+fn finish_replacement(dirs: &config::NodeSparkDirs, version: &str, aside: &Path) -> Result<()> {
+    if dirs.versions_dir.join(version).exists() {
+        fs::remove_dir_all(aside)?;
+        if config::load_config()?.active_version.as_deref() == Some(version) {
+            create_node_symlinks(version)?;
         }
+    } else {
+        verbose::log(&format!("Reinstall of Node.js {} failed, restoring the previous install", version));
+        fs::rename(aside, dirs.versions_dir.join(version))?;
+    }
+    Ok(())
+}
+
+fn resolve_requested(dirs: &config::NodeSparkDirs, version: &str, from_project: bool, options: &InstallOptions) -> Result<String> {
+    let resolved = if options.offline {
+        resolve_cached(dirs, version)?
+    } else if download::is_alias(version) {
+        say!("Fetching {} Node.js version...", version);
+        let index = download::index_for(version)?;
         
-        unix_fs::symlink(&node_path, &node_link)?;
-        unix_fs::symlink(&npm_path, &npm_link)?;
-        unix_fs::symlink(&npx_path, &npx_link)?;
+        download::resolve_remote(version, &index)
+            .ok_or_else(|| NodeSparkError::VersionNotFound(format!("No Node.js release matches {}", version)))?
+    } else if from_project {
+        resolve_preferring_installed(dirs, version)?
+    } else {
+        match utils::parse_version_spec(version)? {
+            VersionSpec::Exact(version) => version,
+            requirement => {
+                say!("Resolving Node.js {}...", version);
+                requirement.resolve(&download::get_available_versions()?)
+                    .ok_or_else(|| NodeSparkError::VersionNotFound(format!("No Node.js release matches {}", version)))?
+            }
+        }
+    };
+    Ok(resolved)
+}
+
+pub fn install_version(version: &str, options: &InstallOptions) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let started = Instant::now();
+    events::emit("install_started", json!({ "version": version }));
+    
+    let platform = options.platform_for(version);
+    if platform != options.platform() {
+        say!("{}", format!("Node.js {} has no Apple Silicon build; installing the x64 build, which runs under Rosetta 2", version).info());
+    }
+    if platform.is_musl() {
+        say!("Using the unofficial {} build", platform.name());
+    }
+    
+    if let Some(ref file) = options.file {
+        install_file(&dirs, version, &platform, file, options)?;
+    } else if options.offline {
+        let archive = archive_cache::lookup(&dirs, version, &platform)
+            .ok_or_else(|| anyhow!("No cached archive for Node.js {}", version))?;
+        say!("Using cached archive {}", archive.display());
+        unpack(&dirs, version, &archive, archive.display().to_string(), options.progress.as_ref())?;
+    } else {
+        download_and_unpack(&dirs, version, &platform, options)?;
+    }
+    
+    events::emit("install_finished", json!({
+        "version": version,
+        "duration_ms": events::elapsed_ms(started),
+    }));
+    
+    Ok(())
+}
+
+fn download_and_unpack(dirs: &config::NodeSparkDirs, version: &str, platform: &Platform, options: &InstallOptions) -> Result<()> {
+    let config = config::load_config()?;
+    let verify_signatures = options.verify_signatures || config.verify_signatures;
+    if options.skip_checksum && verify_signatures {
+        return Err(anyhow!("--no-verify cannot be combined with signature verification"));
+    }
+    if verify_signatures && platform.is_musl() {
+        return Err(anyhow!("Unofficial musl builds are not signed by the Node.js release team, so their signatures cannot be verified"));
+    }
+    if verify_signatures && Channel::of(version) == Channel::Nightly {
+        return Err(anyhow!("Nightly builds are not signed by the Node.js release team, so their signatures cannot be verified"));
+    }
+    
+    if let Some(available) = download::missing_artifact(version, platform) {
+        return Err(NodeSparkError::VersionNotFound(format!(
+            "Node.js {} has no build for {}. Available platforms: {}", version, platform.name(), available.join(", ")
+        )).into());
+    }
+    
+    let shasums_url = platform.shasums_url(version);
+    if let Some(cached) = archive_cache::lookup(dirs, version, platform).filter(|_| !options.force) {
+        say!("Reusing cached archive {}", cached.display());
+        if !options.skip_checksum {
+            let shasums = download::fetch_text(&shasums_url)?;
+            if let Err(e) = verify_checksum(dirs, version, &cached, &shasums, &shasums_url, verify_signatures) {
+                let _ = fs::remove_file(&cached);
+                return Err(e);
+            }
+        }
+        archive_cache::touch(&cached);
+        let source = platform.file_url(version, &file_name(&cached));
+        return unpack(dirs, version, &cached, source, options.progress.as_ref());
+    }
+    
+    let shasums = match download::fetch_text(&shasums_url) {
+        Ok(shasums) => Some(shasums),
+        Err(_) if options.skip_checksum => None,
+        Err(e) => return Err(e),
+    };
+    
+    // Take the first archive format the release lists; without a checksum list fall back to the one every release has
+    let artifact = shasums.as_ref()
+        .and_then(|shasums| platform.listed_artifact(version, shasums))
+        .map(|(name, _)| name)
+        .unwrap_or_else(|| platform.universal_artifact(version));
+    let download_url = platform.file_url(version, &artifact);
+    
+    let temp_dir = dirs.config_dir.join("temp");
+    fs::create_dir_all(&temp_dir)?;
+    let download_path = temp_dir.join(&artifact);
+    
+    let download_guard = interrupt::guard(&download_path);
+    download::download_file(&download_url, &download_path, options.progress.as_ref())?;
+    
+    match shasums {
+        Some(ref shasums) if !options.skip_checksum => {
+            verify_checksum(dirs, version, &download_path, shasums, &shasums_url, verify_signatures)?;
+        }
+        _ => say!("{}", "Skipping checksum verification (--no-verify)".warning()),
+    }
+    
+    unpack(dirs, version, &download_path, download_url, options.progress.as_ref())?;
+    
+    if !options.skip_checksum {
+        if let Err(e) = archive_cache::store(dirs, &download_path) {
+            verbose::log(&format!("Failed to cache archive for Node.js {}: {}", version, e));
+        }
+        if let Some(max_mb) = config.cache_max_mb {
+            for evicted in archive_cache::evict(dirs, archive_cache::max_bytes(max_mb))? {
+                verbose::log(&format!("Evicted {} from the archive cache", evicted.display()));
+            }
+        }
+    }
+    drop(download_guard);
+    
+    Ok(())
+}
+
+fn unpack(dirs: &config::NodeSparkDirs, version: &str, archive: &Path, source: String, progress: Option<&MultiProgress>) -> Result<()> {
+    say!("Extracting Node.js {}...", version);
+    let extract_started = Instant::now();
+    events::emit("extract_started", json!({ "version": version }));
+    let staging_dir = dirs.versions_dir.join(format!(".staging-{}", version));
+    let staging_guard = interrupt::guard(&staging_dir);
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir)?;
+    }
+    fs::create_dir_all(&staging_dir)?;
+    extract::extract_archive(archive, &staging_dir, progress)?;
+    
+    finalize_staging(dirs, version, &staging_dir, source)?;
+    drop(staging_guard);
+    events::emit("extract_finished", json!({
+        "version": version,
+        "duration_ms": events::elapsed_ms(extract_started),
+    }));
+    
+    Ok(())
+}
+
+fn resolve_cached(dirs: &config::NodeSparkDirs, spec: &str) -> Result<String> {
+    let cached = archive_cache::cached_versions(dirs)?;
+    
+    let available = match utils::resolve_spec(spec, &cached) {
+        Some(version) => return Ok(version),
+        None if cached.is_empty() => "The archive cache is empty; run an online install first to populate it.".to_string(),
+        None => format!("Cached versions: {}", cached.join(", ")),
+    };
+    
+    Err(NodeSparkError::VersionNotFound(format!(
+        "Node.js {} is not in the local archive cache, so it cannot be installed offline. {}", spec, available
+    )).into())
+}
+
+pub fn adopt_version(version: &str, dir: &Path) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let source = fs::canonicalize(dir)
+        .map_err(|e| anyhow!("Cannot access {}: {}", dir.display(), e))?;
+    
+    let node_name = if cfg!(target_os = "windows") { "node.exe" } else { "node" };
+    let node_path = utils::version_bin_dir(&source).join(node_name);
+    if !node_path.is_file() {
+        return Err(anyhow!("{} does not look like a Node.js installation: {} not found",
+            source.display(), node_path.display()));
     }
     
-    #[cfg(windows)]
-    {
-        use std::os::windows::fs as windows_fs;
-        if node_link.exists() {
-            fs::remove_file(&node_link)?;
+    if let Some(found) = detect_version(&source) {
+        if found != version {
+            return Err(anyhow!("{} contains Node.js {}, not {}", source.display(), found, version));
         }
-        if npm_link.exists() {
-            fs::remove_file(&npm_link)?;
+    }
+    
+    say!("Copying Node.js {} from {}...", version, source.display());
+    let started = Instant::now();
+    events::emit("adopt_started", json!({ "version": version, "source": source }));
+    
+    let staging_dir = dirs.versions_dir.join(format!(".staging-{}", version));
+    let staging_guard = interrupt::guard(&staging_dir);
+    if staging_dir.exists() {
+        fs::remove_dir_all(&staging_dir)?;
+    }
+    disk::copy_dir(&source, &staging_dir.join(format!("node-v{}", version)))?;
+    
+    finalize_staging(&dirs, version, &staging_dir, format!("adopted from {}", source.display()))?;
+    drop(staging_guard);
+    
+    events::emit("adopt_finished", json!({
+        "version": version,
+        "duration_ms": events::elapsed_ms(started),
+    }));
+    
+    Ok(())
+}
+
+fn check_file(file: &Path, version: &str, platform: &Platform) -> Result<()> {
+    let (file_version, file_platform) = archive_name(file)?;
+    if file_version != version {
+        return Err(anyhow!("{} contains Node.js {}, not {}", file.display(), file_version, version));
+    }
+    if file_platform != platform.name() {
+        return Err(anyhow!(
+            "{} is a {} build, but this machine needs {}. Pass --arch or --flavor to install it anyway.",
+            file.display(), file_platform, platform.name()
+        ));
+    }
+    Ok(())
+}
+
+fn install_file(dirs: &config::NodeSparkDirs, version: &str, platform: &Platform, file: &Path, options: &InstallOptions) -> Result<()> {
+    if options.skip_checksum {
+        say!("{}", "Skipping checksum verification (--no-verify)".warning());
+    } else {
+        let sibling = file.with_file_name("SHASUMS256.txt");
+        let (shasums, source) = if sibling.is_file() {
+            (fs::read_to_string(&sibling)?, sibling.display().to_string())
+        } else {
+            let shasums_url = platform.shasums_url(version);
+            let shasums = download::fetch_text(&shasums_url).map_err(|e| anyhow!(
+                "No SHASUMS256.txt next to {} and it could not be downloaded ({}). Copy the release's SHASUMS256.txt beside the archive or pass --no-verify.",
+                file.display(), e
+            ))?;
+            (shasums, shasums_url)
+        };
+        check_checksum(version, &file_name(file), file, &shasums, &source)?;
+    }
+    
+    unpack(dirs, version, file, format!("installed from {}", file.display()), options.progress.as_ref())
+}
+
+fn archive_name(file: &Path) -> Result<(String, String)> {
+    let name = file_name(file);
+    let parsed = [".tar.gz", ".tar.xz", ".zip"].iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .and_then(|stem| stem.strip_prefix("node-v"))
+        .and_then(|rest| ["-linux-", "-darwin-", "-win-", "-aix-"].iter()
+            .find_map(|os| rest.rfind(os).map(|i| (&rest[..i], &rest[i + 1..]))))
+        .and_then(|(version, platform)| utils::parse_version(version).ok().map(|version| (version, platform.to_string())));
+    
+    parsed.ok_or_else(|| anyhow!(
+        "{} is not a Node.js release archive (expected a name like node-v20.11.1-linux-x64.tar.gz)", file.display()
+    ))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+fn verify_checksum(dirs: &config::NodeSparkDirs, version: &str, archive: &Path, shasums: &str, source: &str, verify_signatures: bool) -> Result<()> {
+    if verify_signatures {
+        say!("Verifying signature of SHASUMS256.txt...");
+        signature::verify_shasums(dirs, version, shasums)?;
+        events::emit("signature_verified", json!({ "version": version }));
+    }
+    
+    check_checksum(version, &file_name(archive), archive, shasums, source)
+}
+
+fn check_checksum(version: &str, file_name: &str, archive: &Path, shasums: &str, source: &str) -> Result<()> {
+    say!("Verifying checksum of {}...", file_name);
+    let expected = download::checksum_for(shasums, file_name)
+        .ok_or_else(|| NodeSparkError::Verification(format!("No checksum for {} in {}", file_name, source)))?;
+    let actual = receipt::hash_file(archive)?;
+    
+    if !expected.eq_ignore_ascii_case(&actual) {
+        events::emit("checksum_failed", json!({ "version": version, "expected": expected, "actual": actual }));
+        return Err(NodeSparkError::Verification(format!(
+            "Checksum mismatch for {}: expected {}, got {}. The download is corrupted or was tampered with, so it was not extracted.",
+            file_name, expected, actual
+        )).into());
+    }
+    
+    events::emit("checksum_verified", json!({ "version": version, "sha256": actual }));
+    Ok(())
+}
+
+fn detect_version(dir: &Path) -> Option<String> {
+    let header = fs::read_to_string(dir.join("include/node/node_version.h")).ok();
+    if let Some(header) = header {
+        let component = |name: &str| header.lines()
+            .filter_map(|line| line.trim().strip_prefix("#define "))
+            .find_map(|line| line.strip_prefix(name)?.trim().parse::<u64>().ok());
+        
+        if let (Some(major), Some(minor), Some(patch)) = (
+            component("NODE_MAJOR_VERSION"),
+            component("NODE_MINOR_VERSION"),
+            component("NODE_PATCH_VERSION"),
+        ) {
+            return Some(format!("{}.{}.{}", major, minor, patch));
         }
-        if npx_link.exists() {
-            fs::remove_file(&npx_link)?;
+    }
+    
+    let node_name = if cfg!(target_os = "windows") { "node.exe" } else { "node" };
+    let output = std::process::Command::new(utils::version_bin_dir(dir).join(node_name))
+        .arg("--version")
+        .output()
+        .ok()?;
+    
+    if !output.status.success() {
+        return None;
+    }
+    utils::parse_version(String::from_utf8_lossy(&output.stdout).trim()).ok()
+}
+
+fn finalize_staging(dirs: &config::NodeSparkDirs, version: &str, staging_dir: &Path, source: String) -> Result<()> {
+    permissions::apply_configured(&config::load_config()?, staging_dir)?;
+    
+    let extracted = extracted_root(staging_dir)?;
+    let files = receipt::hash_tree(&extracted)?;
+    let version_dir = dirs.versions_dir.join(version);
+    // Until the receipt is written, an interrupt moves the install back into staging for cleanup
+    let restore_guard = interrupt::restore_on_interrupt(&version_dir, &extracted);
+    fs::rename(&extracted, &version_dir)?;
+    receipt::save(dirs, &receipt::Receipt::new(version, Some(source), files))?;
+    drop(restore_guard);
+    
+    Ok(())
+}
+
+// A project's range should not trigger a download when a matching version is already installed
+fn resolve_preferring_installed(dirs: &config::NodeSparkDirs, spec: &str) -> Result<String> {
+    if !download::is_alias(spec) {
+        if let Some(version) = utils::resolve_spec(spec, &utils::installed_versions(&dirs.versions_dir)?) {
+            return Ok(version);
         }
+    }
+    
+    say!("Resolving Node.js {}...", spec);
+    Ok(download::resolve_remote(spec, &download::index_for(spec)?)
+        .ok_or_else(|| NodeSparkError::VersionNotFound(format!("No Node.js release matches {}", spec)))?)
+}
+
+pub fn ensure_installed(spec: &str) -> Result<String> {
+    let dirs = config::get_dirs()?;
+    let version = resolve_preferring_installed(&dirs, spec)?;
+    restore_replaced(&dirs, &version)?;
+    if dirs.versions_dir.join(&version).exists() {
+        return Ok(version);
+    }
+    
+    say!("Installing Node.js {}", version.success());
+    install_version(&version, &InstallOptions::default())?;
+    say!("Successfully installed Node.js {}", version.success());
+    
+    Ok(version)
+}
+
+fn extracted_root(staging_dir: &Path) -> Result<PathBuf> {
+    let entries: Vec<_> = fs::read_dir(staging_dir)?.collect::<std::io::Result<_>>()?;
+    
+    if entries.len() == 1 && entries[0].file_type()?.is_dir() {
+        Ok(entries[0].path())
+    } else {
+        Ok(staging_dir.to_path_buf())
+    }
+}
+
+pub fn create_node_symlinks(version: &str) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+    let version_bin_dir = utils::version_bin_dir(&dirs.versions_dir.join(version));
+    
+    let mut env = BTreeMap::new();
+    if let Some(nodedir) = headers::nodedir(&dirs, version) {
+        env.insert("npm_config_nodedir".to_string(), nodedir.to_string_lossy().to_string());
+    }
+    if let Some(version_env) = config.version_env.get(version) {
+        env.extend(version_env.clone());
+    }
+    
+    for name in ["node", "npm", "npx"] {
+        let target = shim::target(&version_bin_dir, name);
+        let link = dirs.bin_dir.join(name);
         
-        windows_fs::symlink_file(&node_path, &node_link)?;
-        windows_fs::symlink_file(&npm_path, &npm_link)?;
-        windows_fs::symlink_file(&npx_path, &npx_link)?;
+        shim::remove(&link)?;
+        if !target.exists() {
+            continue;
+        }
+        if shim::needs_wrapper(&env) {
+            shim::write_wrapper(&link, &target, &env)?;
+        } else {
+            shim::symlink(&target, &link)?;
+        }
     }
     
+    permissions::apply_configured(&config, &dirs.bin_dir)?;
+    
     Ok(())
 }
@@ -0,0 +1,81 @@
+use anyhow::{Result, anyhow};
+use std::fs;
+use std::path::Path;
+use crate::config;
+use crate::options::symbols;
+use crate::options::theme::Themed;
+use crate::utils::{self, receipt, shim};
+use crate::say;
+
+pub fn execute(dir: &Path, name: &str) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let version = utils::parse_version(name)?;
+    let source = fs::canonicalize(dir)
+        .map_err(|e| anyhow!("Cannot access {}: {}", dir.display(), e))?;
+
+    let version_dir = dirs.versions_dir.join(&version);
+    if version_dir.symlink_metadata().is_ok() {
+        return Err(anyhow!("Node.js {} already exists. Choose another name with --as.", version));
+    }
+
+    let node_name = if cfg!(target_os = "windows") { "node.exe" } else { "node" };
+
+    if utils::version_bin_dir(&source).join(node_name).is_file() {
+        shim::link_dir(&source, &version_dir)?;
+    } else if source.join(node_name).is_file() {
+        let bin_dir = utils::version_bin_dir(&version_dir);
+        fs::create_dir_all(&bin_dir)?;
+        for name in ["node", "npm", "npx"] {
+            let file_name = if name == "node" { node_name } else { name };
+            let target = source.join(file_name);
+            if target.exists() {
+                shim::symlink(&target, &bin_dir.join(file_name))?;
+            }
+        }
+    } else {
+        return Err(anyhow!("No {} binary found in {} or its bin directory", node_name, source.display()));
+    }
+
+    let mut config = config::load_config()?;
+    config.linked_versions.insert(version.clone(), source.clone());
+    config::save_config(&config)?;
+
+    receipt::save(&dirs, &receipt::Receipt::new(
+        &version,
+        Some(format!("linked from {}", source.display())),
+        Default::default(),
+    ))?;
+
+    say!("Linked Node.js {} {} {}", version.success(), symbols::arrow(), source.display());
+    Ok(())
+}
+
+pub fn unlink(name: &str) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let version = utils::parse_version(name)?;
+    let mut config = config::load_config()?;
+
+    if !config.linked_versions.contains_key(&version) {
+        return Err(anyhow!("Node.js {} is not a linked version", version));
+    }
+
+    if config.active_version.as_deref() == Some(version.as_str()) {
+        return Err(anyhow!("Cannot unlink the active Node.js version. Switch to another version first."));
+    }
+
+    let version_dir = dirs.versions_dir.join(&version);
+    let metadata = version_dir.symlink_metadata();
+    if metadata.as_ref().map(|m| m.file_type().is_symlink()).unwrap_or(false) {
+        shim::remove_dir_link(&version_dir)?;
+    } else if metadata.is_ok() {
+        fs::remove_dir_all(&version_dir)?;
+    }
+
+    receipt::remove(&dirs, &version)?;
+    config.linked_versions.remove(&version);
+    config.last_used.remove(&version);
+    config::save_config(&config)?;
+
+    say!("Unlinked Node.js {}", version.success());
+    Ok(())
+}
@@ -1,106 +1,323 @@
-use anyhow::Result;
-use colored::Colorize;
-use std::fs;
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use crate::commands::recent;
 use crate::config;
-use crate::utils::download;
+use std::path::{Path, PathBuf};
+use crate::utils::{self, disk, download};
+use crate::utils::channel::Channel;
+use crate::utils::schedule::{self, Phase, Schedule};
+use crate::options::{output, symbols};
+use crate::options::theme::Themed;
+use crate::say;
 
-pub fn execute(remote: bool) -> Result<()> {
-    if remote {
-        list_remote_versions()?;
+#[derive(Default)]
+pub struct ListOptions {
+    pub remote: bool,
+    pub security: bool,
+    pub lts: bool,
+    pub filter: Option<String>,
+    pub channel: Channel,
+    pub all: bool,
+    pub limit: Option<usize>,
+    pub size: bool,
+    pub json: bool,
+    pub offline: bool,
+}
+
+pub fn execute(options: &ListOptions) -> Result<()> {
+    if options.json {
+        let entries = if options.remote { remote_json(options)? } else { local_json(options)? };
+        output::print_json(&entries)?;
+    } else if options.remote {
+        list_remote_versions(options)?;
     } else {
-        list_local_versions()?;
+        list_local_versions(options)?;
     }
     
     Ok(())
 }
 
-fn list_local_versions() -> Result<()> {
+fn version_sizes(versions_dir: &Path, versions: &[String], enabled: bool) -> Vec<Option<u64>> {
+    if !enabled {
+        return vec![None; versions.len()];
+    }
+    let paths: Vec<PathBuf> = versions.iter().map(|version| versions_dir.join(version)).collect();
+    disk::dir_sizes(&paths).into_iter().map(Some).collect()
+}
+
+fn local_json(options: &ListOptions) -> Result<Vec<Value>> {
     let dirs = config::get_dirs()?;
     let config = config::load_config()?;
+    let versions = utils::installed_versions(&dirs.versions_dir)?;
+    let index = download::cached_remote_index().unwrap_or_default();
+    let sizes = version_sizes(&dirs.versions_dir, &versions, options.size);
+    let schedule = schedule::cached();
     
-    println!("Installed Node.js versions:");
-    
-    let entries = match fs::read_dir(&dirs.versions_dir) {
-        Ok(entries) => entries,
-        Err(_) => {
-            println!("  No versions installed");
-            return Ok(());
-        }
+    Ok(versions.iter().zip(sizes).map(|(version, size)| {
+        let remote = index.iter().find(|remote| &remote.version == version);
+        let aliases: Vec<&String> = config.aliases.iter()
+            .filter(|(_, target)| utils::resolve_spec(target, &versions).as_ref() == Some(version))
+            .map(|(name, _)| name)
+            .collect();
+        json!({
+            "version": version,
+            "installed": true,
+            "active": config.active_version.as_ref() == Some(version),
+            "lts": remote.and_then(|remote| remote.lts.clone()),
+            "date": remote.map(|remote| remote.date.clone()),
+            "status": if Channel::of(version) == Channel::Nightly { Some("nightly") } else { phase_name(schedule.as_ref(), version) },
+            "npm": local_npm_version(&dirs.versions_dir, version, remote),
+            "aliases": aliases,
+            "arch": config.version_arch.get(version).map(String::as_str).unwrap_or(utils::host_arch()),
+            "bytes": size,
+        })
+    }).collect())
+}
+
+fn remote_index(options: &ListOptions) -> Result<Vec<download::RemoteVersion>> {
+    let mut index = if options.offline {
+        download::cached_channel_index(options.channel).ok_or_else(|| anyhow!(
+            "No cached {} version index yet; run 'node-spark list --remote' once while online", options.channel.name()
+        ))?
+    } else {
+        download::get_channel_index(options.channel)?
     };
-    
-    let mut versions = Vec::new();
-    for entry in entries {
-        let entry = entry?;
-        if entry.file_type()?.is_dir() {
-            if let Some(name) = entry.file_name().to_str() {
-                versions.push(name.to_string());
-            }
-        }
+    if options.security {
+        index.retain(|remote| remote.security);
+    }
+    if options.lts {
+        index.retain(|remote| remote.lts.is_some());
     }
+    if let Some(ref filter) = options.filter {
+        let spec = utils::parse_version_spec(filter)?;
+        // Prerelease channels match on the release they lead up to, so "22" lists 22.0.0-rc.1
+        index.retain(|remote| spec.matches(&remote.version)
+            || remote.version.split_once('-').is_some_and(|(release, _)| spec.matches(release)));
+    }
+    Ok(index)
+}
+
+fn remote_json(options: &ListOptions) -> Result<Vec<Value>> {
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+    let index = remote_index(options)?;
+    let schedule = remote_schedule(options);
+    
+    Ok(index.iter().take(options.limit.unwrap_or(usize::MAX)).map(|remote| json!({
+        "version": remote.version,
+        "installed": dirs.versions_dir.join(&remote.version).exists(),
+        "active": config.active_version.as_ref() == Some(&remote.version),
+        "lts": remote.lts,
+        "date": remote.date,
+        "status": phase_name(schedule.as_ref(), &remote.version),
+        "npm": remote.npm,
+        "security": remote.security,
+    })).collect())
+}
+
+fn list_local_versions(options: &ListOptions) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+    
+    say!("Installed Node.js versions:");
+    
+    let versions = utils::installed_versions(&dirs.versions_dir)?;
     
     if versions.is_empty() {
-        println!("  No versions installed");
+        say!("  No versions installed");
         return Ok(());
     }
     
-    versions.sort_by(|a, b| {
-        match (semver::Version::parse(a), semver::Version::parse(b)) {
-            (Ok(a_ver), Ok(b_ver)) => a_ver.cmp(&b_ver).reverse(),
-            (Ok(_), Err(_)) => std::cmp::Ordering::Less,
-            (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
-            (Err(_), Err(_)) => a.cmp(b).reverse()
+    let sizes = version_sizes(&dirs.versions_dir, &versions, options.size);
+    let schedule = schedule::cached();
+    let index = download::cached_remote_index().unwrap_or_default();
+    for (version, size) in versions.iter().zip(&sizes) {
+        let padded = format!("{:<10}", version);
+        let mut usage = recent::usage_summary(&dirs, &config, version).muted().to_string();
+        let remote = index.iter().find(|remote| &remote.version == version);
+        if let Some(npm) = local_npm_version(&dirs.versions_dir, version, remote) {
+            usage = format!("{}  {}", format!("npm {:<8}", npm).muted(), usage);
+        }
+        if Channel::of(version) == Channel::Nightly {
+            usage = format!("{}  {}", "Nightly".warning(), usage);
+        } else if let Some(phase) = schedule.as_ref().and_then(|schedule| schedule.phase(version)) {
+            usage = format!("{}  {}", phase.badge(), usage);
+        }
+        if let Some(size) = size {
+            usage = format!("{:>10}  {}", disk::format_size(*size), usage);
+        }
+        
+        let aliases: Vec<&str> = config.aliases.iter()
+            .filter(|(_, target)| utils::resolve_spec(target, &versions).as_ref() == Some(version))
+            .map(|(name, _)| name.as_str())
+            .collect();
+        if !aliases.is_empty() {
+            usage = format!("{} {}", usage, format!("[{}]", aliases.join(", ")).info());
+        }
+        if let Some(arch) = config.version_arch.get(version) {
+            usage = format!("{} {}", usage, format!("({})", arch).emphasis());
         }
-    });
-    
-    for version in versions {
-        if let Some(ref active) = config.active_version {
-            if version == *active {
-                println!("* {} (current)", version.green());
-            } else {
-                println!("  {}", version);
-            }
+        
+        if config.active_version.as_ref() == Some(version) {
+            say!("* {} {} (current)", padded.current(), usage);
         } else {
-            println!("  {}", version);
+            say!("  {} {}", padded, usage);
         }
     }
     
+    if options.size {
+        let total: u64 = sizes.iter().flatten().sum();
+        say!("\nTotal: {} in {} version(s)", disk::format_size(total).success(), versions.len());
+    }
+    
     Ok(())
 }
 
-fn list_remote_versions() -> Result<()> {
-    println!("Fetching available Node.js versions...");
+fn list_remote_versions(options: &ListOptions) -> Result<()> {
+    match options.channel {
+        _ if options.offline => {}
+        Channel::Release => say!("Fetching available Node.js versions..."),
+        channel => say!("Fetching available Node.js {} builds...", channel.name()),
+    }
     
-    let available_versions = download::get_available_versions()?;
+    let available_versions = remote_index(options)?;
     
     if available_versions.is_empty() {
-        println!("No available versions found");
+        match options.filter {
+            Some(ref filter) => say!("No available versions match {}", filter),
+            None => say!("No available versions found"),
+        }
         return Ok(());
     }
     
-    println!("\nAvailable Node.js versions:");
+    say!("\nAvailable Node.js versions:");
     
     let config = config::load_config()?;
     let dirs = config::get_dirs()?;
+    let installed_versions = utils::installed_versions(&dirs.versions_dir)?;
+    let schedule = remote_schedule(options);
     
-    for (i, version) in available_versions.iter().enumerate().take(30) {
-        let installed = dirs.versions_dir.join(version).exists();
-        let is_current = config.active_version.as_ref().map_or(false, |v| v == version);
+    // --limit keeps the newest N releases overall and shows each of their groups in full
+    let listed = &available_versions[..options.limit.unwrap_or(usize::MAX).min(available_versions.len())];
+    let groups = if options.lts {
+        group_releases(listed, |remote| remote.lts.clone())
+    } else {
+        group_releases(listed, |remote| utils::major_version(&remote.version).map(|major| major.to_string()))
+    };
+    
+    let mut hidden = available_versions.len() - listed.len();
+    for (key, releases) in &groups {
+        let major = utils::major_version(&releases[0].version).unwrap_or_default();
+        let title = match (options.lts, &releases[0].lts) {
+            (true, _) => format!("{} ({}.x)", key.brand(), major),
+            (false, Some(codename)) => format!("{} (LTS {})", format!("{}.x", key).brand(), codename),
+            (false, None) => format!("{}.x", key).brand().to_string(),
+        };
+        say!("\n{} {}", title, format!("{} release(s)", releases.len()).muted());
         
-        if installed {
-            if is_current {
-                println!("* {} (installed, current)", version.green());
-            } else {
-                println!("* {} (installed)", version.yellow());
-            }
-        } else {
-            println!("  {}", version);
+        let shown = if options.all || options.limit.is_some() { releases.len() } else { RELEASES_PER_GROUP };
+        for remote in releases.iter().take(shown) {
+            print_remote(remote, &config, &dirs, &installed_versions, schedule.as_ref());
         }
-        
-        if i == 29 {
-            println!("  ... and more");
-            break;
+        if releases.len() > shown {
+            say!("  {}", format!("... and {} older release(s)", releases.len() - shown).muted());
+            hidden += releases.len() - shown;
         }
     }
     
+    if hidden > 0 {
+        say!("\n{} older release(s) hidden, run with --all to show them", hidden);
+    }
+    
     Ok(())
 }
+
+const RELEASES_PER_GROUP: usize = 3;
+
+fn group_releases(
+    index: &[download::RemoteVersion],
+    key: impl Fn(&download::RemoteVersion) -> Option<String>,
+) -> Vec<(String, Vec<&download::RemoteVersion>)> {
+    let mut groups: Vec<(String, Vec<&download::RemoteVersion>)> = Vec::new();
+    for remote in index {
+        let Some(key) = key(remote) else { continue };
+        match groups.iter_mut().find(|(name, _)| *name == key) {
+            Some((_, releases)) => releases.push(remote),
+            None => groups.push((key, vec![remote])),
+        }
+    }
+    groups
+}
+
+fn print_remote(
+    remote: &download::RemoteVersion,
+    config: &config::Config,
+    dirs: &config::NodeSparkDirs,
+    installed_versions: &[String],
+    schedule: Option<&Schedule>,
+) {
+    let version = &remote.version;
+    let installed = dirs.versions_dir.join(version).exists();
+    let is_current = config.active_version.as_ref() == Some(version);
+    let padded = format!("{:<10}", version);
+    let details = remote_details(remote, schedule.and_then(|schedule| schedule.phase(version)));
+    
+    if installed {
+        if is_current {
+            say!("* {} {} (installed, current)", padded.current(), details);
+        } else {
+            say!("* {} {} (installed)", padded.installed(), details);
+        }
+    } else if let Some(older) = outdated_installed(version, installed_versions) {
+        say!("  {} {}  {}", padded, details,
+            format!("{} newer than installed {}", symbols::back_arrow(), older).warning());
+    } else {
+        say!("  {} {}", padded, details);
+    }
+}
+
+fn outdated_installed(version: &str, installed: &[String]) -> Option<String> {
+    let remote = semver::Version::parse(version).ok()?;
+    
+    let (newest, parsed) = installed.iter()
+        .filter_map(|v| semver::Version::parse(v).ok().map(|parsed| (v, parsed)))
+        .filter(|(_, parsed)| parsed.major == remote.major)
+        .max_by(|a, b| a.1.cmp(&b.1))?;
+    
+    if parsed < remote {
+        Some(newest.clone())
+    } else {
+        None
+    }
+}
+
+fn local_npm_version(versions_dir: &Path, version: &str, remote: Option<&download::RemoteVersion>) -> Option<String> {
+    utils::installed_npm_version(&versions_dir.join(version))
+        .or_else(|| remote.and_then(|remote| remote.npm.clone()))
+}
+
+fn remote_schedule(options: &ListOptions) -> Option<Schedule> {
+    if options.offline { schedule::cached() } else { schedule::load() }
+}
+
+fn phase_name(schedule: Option<&Schedule>, version: &str) -> Option<&'static str> {
+    schedule?.phase(version).map(|phase| phase.name())
+}
+
+fn remote_details(remote: &download::RemoteVersion, phase: Option<Phase>) -> String {
+    let mut details = vec![remote.date.muted().to_string()];
+    if let Some(ref npm) = remote.npm {
+        details.push(format!("npm {}", npm).muted().to_string());
+    }
+    
+    match (phase, &remote.lts) {
+        (Some(phase), _) => details.push(phase.badge()),
+        (None, Some(lts)) => details.push(format!("LTS: {}", lts).info().to_string()),
+        (None, None) => {}
+    }
+    if remote.security {
+        details.push(format!("{} security", symbols::shield()).error().to_string());
+    }
+    
+    details.join("  ")
+}
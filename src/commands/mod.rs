@@ -3,4 +3,39 @@ pub mod r#use;
 pub mod list;
 pub mod remove;
 pub mod global_list;
-pub mod update;
\ No newline at end of file
+pub mod update;
+pub mod status;
+pub mod exec;
+pub mod version_env;
+pub mod run;
+pub mod dlx;
+pub mod projects;
+pub mod upgrade_node;
+pub mod auto_update;
+pub mod doctor;
+pub mod verify;
+pub mod recent;
+pub mod ensure;
+pub mod generate;
+pub mod link;
+pub mod telemetry;
+pub mod bug_report;
+pub mod headers;
+pub mod gc;
+pub mod url;
+pub mod daemon;
+pub mod resolve;
+pub mod pin;
+pub mod env;
+pub mod init;
+pub mod completions;
+pub mod alias;
+pub mod which;
+pub mod setup;
+pub mod prune;
+pub mod cache;
+pub mod outdated;
+pub mod upgrade;
+pub mod reinstall;
+pub mod audit_versions;
+pub mod info;
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use crate::config;
+use crate::options::{output, symbols, verbose};
+use crate::options::theme::Themed;
+use crate::utils::{self, download};
+use crate::say;
+
+struct Outdated {
+    version: String,
+    newest: String,
+    newest_installed: bool,
+    security_fix: Option<String>,
+}
+
+pub fn execute(as_json: bool) -> Result<()> {
+    verbose::log("Executing outdated command");
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+    let installed: Vec<String> = utils::installed_releases(&dirs.versions_dir)?
+        .into_iter()
+        .filter(|version| !config.linked_versions.contains_key(version))
+        .collect();
+
+    if !as_json {
+        say!("Checking installed versions against the newest release of each major...");
+    }
+    let index = download::get_remote_index()?;
+
+    let outdated: Vec<Outdated> = installed.iter()
+        .filter_map(|version| {
+            let newest = download::newest_in_major(version, &index)?;
+            if newest.version == *version {
+                return None;
+            }
+            Some(Outdated {
+                version: version.clone(),
+                newest: newest.version.clone(),
+                newest_installed: installed.contains(&newest.version),
+                security_fix: download::missing_security_release(version, &index).map(|fix| fix.version.clone()),
+            })
+        })
+        .collect();
+
+    if as_json {
+        let entries: Vec<Value> = outdated.iter().map(|entry| json!({
+            "version": entry.version,
+            "newest": entry.newest,
+            "newest_installed": entry.newest_installed,
+            "active": config.active_version.as_ref() == Some(&entry.version),
+            "security_fix": entry.security_fix,
+        })).collect();
+        return output::print_json(&entries);
+    }
+
+    if outdated.is_empty() {
+        say!("{}", "All installed versions are the newest release of their major".success());
+        return Ok(());
+    }
+
+    say!();
+    for entry in &outdated {
+        let mut notes = Vec::new();
+        if config.active_version.as_ref() == Some(&entry.version) {
+            notes.push("current".current().to_string());
+        }
+        if entry.newest_installed {
+            notes.push("newest already installed".muted().to_string());
+        }
+        if let Some(ref fix) = entry.security_fix {
+            notes.push(format!("{} security fix in {}", symbols::shield(), fix).error().to_string());
+        }
+
+        say!(
+            "  {:<10} {} {:<10} {}",
+            entry.version,
+            symbols::arrow(),
+            entry.newest.success(),
+            notes.join(", ")
+        );
+    }
+
+    say!("\n{} of {} installed version(s) outdated", outdated.len(), installed.len());
+    Ok(())
+}
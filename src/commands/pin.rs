@@ -0,0 +1,31 @@
+use anyhow::{Result, anyhow};
+use std::env;
+use std::fs;
+use crate::utils::project_version::{self, MISE_FILES};
+use crate::options::theme::Themed;
+use crate::say;
+
+pub fn execute(version: &str, mise: bool) -> Result<()> {
+    let spec = version.trim().trim_start_matches('v');
+    if spec.is_empty() {
+        return Err(anyhow!("No version given to pin"));
+    }
+
+    let current_dir = env::current_dir()?;
+
+    let path = if mise {
+        let path = MISE_FILES.iter()
+            .map(|name| current_dir.join(name))
+            .find(|path| path.is_file())
+            .unwrap_or_else(|| current_dir.join("mise.toml"));
+        project_version::write_mise_node(&path, spec)?;
+        path
+    } else {
+        let path = current_dir.join(".nvmrc");
+        fs::write(&path, format!("{}\n", spec))?;
+        path
+    };
+
+    say!("Pinned Node.js {} in {}", spec.success(), path.display());
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::config;
+use crate::options::verbose;
+use crate::utils::{self, project_version::{self, ProjectVersion}};
+use crate::options::theme::Themed;
+use crate::say;
+
+const SKIPPED_DIRS: [&str; 3] = ["node_modules", "target", "dist"];
+
+pub fn scan(dir: Option<&Path>) -> Result<()> {
+    let root = match dir {
+        Some(dir) => dir.to_path_buf(),
+        None => env::current_dir()?,
+    };
+    let dirs = config::get_dirs()?;
+    let installed = utils::installed_versions(&dirs.versions_dir)?;
+
+    say!("Scanning {} for Node.js version declarations...", root.display());
+
+    let mut projects = Vec::new();
+    collect_projects(&root, &mut projects)?;
+
+    if projects.is_empty() {
+        say!("  No version declarations found");
+        return Ok(());
+    }
+
+    let mut required: BTreeMap<String, usize> = BTreeMap::new();
+    let mut conflicts = Vec::new();
+
+    for (project_dir, declarations) in &projects {
+        let display_dir = project_dir.strip_prefix(&root).unwrap_or(project_dir);
+        let display_dir = if display_dir.as_os_str().is_empty() { Path::new(".") } else { display_dir };
+        say!("\n  {}", display_dir.display().to_string().emphasis());
+
+        for declaration in declarations {
+            *required.entry(declaration.spec.clone()).or_default() += 1;
+
+            let file = source_name(declaration);
+            match find_installed(&declaration.spec, &installed) {
+                Some(version) => say!("    {:<14} {:<16} {}", file, declaration.spec, format!("installed ({})", version).success()),
+                None => say!("    {:<14} {:<16} {}", file, declaration.spec, "not installed".warning()),
+            }
+        }
+
+        for conflict in find_conflicts(declarations, &installed) {
+            conflicts.push(format!("{}: {}", display_dir.display(), conflict));
+        }
+    }
+
+    say!("\nRequired versions:");
+    for (spec, count) in &required {
+        let status = match find_installed(spec, &installed) {
+            Some(_) => "installed".success(),
+            None => "missing".warning(),
+        };
+        say!("  {:<16} {} project file(s), {}", spec, count, status);
+    }
+
+    if !conflicts.is_empty() {
+        say!("\n{}", "Conflicts:".error());
+        for conflict in conflicts {
+            say!("  {}", conflict);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn collect_projects(dir: &Path, projects: &mut Vec<(PathBuf, Vec<ProjectVersion>)>) -> Result<()> {
+    let declarations = project_version::declarations(dir)?;
+    if !declarations.is_empty() {
+        projects.push((dir.to_path_buf(), declarations));
+    }
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            verbose::log(&format!("Skipping {}: {}", dir.display(), e));
+            return Ok(());
+        }
+    };
+
+    let mut subdirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str())
+        })
+        .map(|entry| entry.path())
+        .collect();
+    subdirs.sort();
+
+    for subdir in subdirs {
+        collect_projects(&subdir, projects)?;
+    }
+
+    Ok(())
+}
+
+fn find_installed(spec: &str, installed: &[String]) -> Option<String> {
+    installed.iter()
+        .find(|version| utils::version_satisfies(spec, version))
+        .cloned()
+}
+
+fn find_conflicts(declarations: &[ProjectVersion], installed: &[String]) -> Vec<String> {
+    let (ranges, pins): (Vec<&ProjectVersion>, Vec<&ProjectVersion>) = declarations.iter()
+        .partition(|declaration| source_name(declaration) == "package.json");
+
+    let mut conflicts = Vec::new();
+
+    for (i, a) in pins.iter().enumerate() {
+        for b in &pins[i + 1..] {
+            if !utils::version_matches(&a.spec, &b.spec) && !utils::version_matches(&b.spec, &a.spec) {
+                conflicts.push(format!("{} pins {} but {} pins {}",
+                    source_name(a), a.spec, source_name(b), b.spec));
+            }
+        }
+
+        let candidates = match utils::resolve_spec(&a.spec, installed) {
+            Some(version) => vec![version],
+            None => pin_bounds(&a.spec),
+        };
+
+        if !candidates.is_empty() {
+            for range in &ranges {
+                if !candidates.iter().any(|candidate| utils::version_satisfies(&range.spec, candidate)) {
+                    conflicts.push(format!("{} pins {} but engines.node requires {}",
+                        source_name(a), a.spec, range.spec));
+                }
+            }
+        }
+    }
+
+    conflicts
+}
+
+fn pin_bounds(spec: &str) -> Vec<String> {
+    let parts: Vec<&str> = spec.split('.').collect();
+    if parts.is_empty() || parts.len() > 3 || parts.iter().any(|p| p.parse::<u64>().is_err()) {
+        return Vec::new();
+    }
+
+    let pad = |fill: &str| {
+        let mut padded: Vec<&str> = parts.clone();
+        padded.resize(3, fill);
+        padded.join(".")
+    };
+
+    vec![pad("0"), pad("999999")]
+}
+
+fn source_name(declaration: &ProjectVersion) -> String {
+    declaration.source.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
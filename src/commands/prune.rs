@@ -0,0 +1,97 @@
+use anyhow::{Result, anyhow};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::commands::{projects, r#use, remove};
+use crate::config;
+use crate::options::{prompt, verbose};
+use crate::options::theme::Themed;
+use crate::utils::{self, disk};
+use crate::say;
+
+pub fn execute(roots: &[PathBuf], policy: bool, dry_run: bool) -> Result<()> {
+    verbose::log("Executing prune command");
+    let dirs = config::get_dirs()?;
+    let mut config = config::load_config()?;
+    let installed = utils::installed_versions(&dirs.versions_dir)?;
+
+    let mut roots = roots.to_vec();
+    roots.extend(config.workspace_roots.iter().cloned());
+
+    let mut kept: BTreeMap<String, String> = BTreeMap::new();
+    if let Some(ref active) = config.active_version {
+        kept.insert(active.clone(), "active".to_string());
+    }
+    for version in &config.locked_versions {
+        kept.insert(version.clone(), "locked".to_string());
+    }
+    for root in &roots {
+        for (version, source) in pinned_versions(root, &installed)? {
+            kept.entry(version).or_insert(source);
+        }
+    }
+
+    let candidates: Vec<String> = if policy {
+        let keep = config.keep_per_major
+            .ok_or_else(|| anyhow!("--policy needs keep_per_major to be set in the config"))?
+            .max(1);
+        remove::superseded_versions(&config, &installed, keep)
+    } else {
+        installed.clone()
+    };
+    let candidates: Vec<String> = candidates.into_iter()
+        .filter(|version| !kept.contains_key(version))
+        .filter(|version| !config.linked_versions.contains_key(version))
+        .collect();
+
+    for (version, reason) in &kept {
+        if installed.contains(version) {
+            say!("  Keeping  {:<12} {}", version.success(), reason.muted());
+        }
+    }
+
+    if candidates.is_empty() {
+        say!("Nothing to prune");
+        return Ok(());
+    }
+
+    let mut freed = 0;
+    for version in &candidates {
+        let size = disk::dir_size(&dirs.versions_dir.join(version));
+        freed += size;
+        say!("  Removing {:<12} {}", version.warning(), disk::format_size(size));
+    }
+
+    if dry_run {
+        say!("Would remove {} version(s), freeing {}", candidates.len(), disk::format_size(freed).success());
+        return Ok(());
+    }
+    if !prompt::confirm(&format!("Remove {} version(s), freeing {}?", candidates.len(), disk::format_size(freed)))? {
+        say!("Nothing removed");
+        return Ok(());
+    }
+
+    for version in &candidates {
+        remove::remove_installed(&dirs, &mut config, version)?;
+    }
+    config::save_config(&config)?;
+    say!("Removed {} version(s), freed {}", candidates.len(), disk::format_size(freed).success());
+
+    Ok(())
+}
+
+fn pinned_versions(root: &Path, installed: &[String]) -> Result<Vec<(String, String)>> {
+    let mut projects = Vec::new();
+    projects::collect_projects(root, &mut projects)?;
+
+    let mut pinned = Vec::new();
+    for (_, declarations) in projects {
+        for declaration in declarations {
+            let spec = utils::expand_alias(&declaration.spec);
+            if let Some(version) = r#use::resolve_installed_spec(&spec, installed) {
+                pinned.push((version, format!("pinned by {}", declaration.source.display())));
+            }
+        }
+    }
+
+    Ok(pinned)
+}
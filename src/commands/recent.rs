@@ -0,0 +1,57 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use crate::config::{self, Config, NodeSparkDirs};
+use crate::options::output;
+use crate::utils::{self, receipt};
+use crate::options::theme::Themed;
+use crate::say;
+
+pub fn execute(limit: usize) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+
+    let mut used: Vec<(&String, &u64)> = config.last_used.iter()
+        .filter(|(version, _)| dirs.versions_dir.join(version).exists())
+        .collect();
+    used.sort_by(|a, b| b.1.cmp(a.1));
+
+    if output::is_json() {
+        let entries: Vec<Value> = used.into_iter().take(limit).map(|(version, last_used)| json!({
+            "version": version,
+            "last_used": last_used,
+            "active": config.active_version.as_ref() == Some(version),
+        })).collect();
+        return output::print_json(&entries);
+    }
+
+    if used.is_empty() {
+        say!("No recently used Node.js versions");
+        return Ok(());
+    }
+
+    say!("Recently used Node.js versions:");
+    for (version, _) in used.into_iter().take(limit) {
+        let padded = format!("{:<10}", version);
+        let usage = usage_summary(&dirs, &config, version);
+
+        if config.active_version.as_ref() == Some(version) {
+            say!("* {} {} (current)", padded.current(), usage);
+        } else {
+            say!("  {} {}", padded, usage);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn usage_summary(dirs: &NodeSparkDirs, config: &Config, version: &str) -> String {
+    let last_used = match config.last_used.get(version) {
+        Some(timestamp) => format!("last used {}", utils::format_age(*timestamp)),
+        None => "never used".to_string(),
+    };
+
+    match receipt::installed_at(dirs, version) {
+        Some(installed_at) => format!("installed {}, {}", utils::format_age(installed_at), last_used),
+        None => last_used,
+    }
+}
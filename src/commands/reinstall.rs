@@ -0,0 +1,63 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use crate::commands::install::{self, InstallOptions};
+use crate::config;
+use crate::error::NodeSparkError;
+use crate::options::verbose;
+use crate::options::theme::Themed;
+use crate::utils::{self, npm_globals};
+use crate::say;
+
+pub fn execute(version: &str) -> Result<()> {
+    verbose::log("Executing reinstall command");
+    let dirs = config::get_dirs()?;
+    let version = utils::resolve_spec(version, &utils::installed_versions(&dirs.versions_dir)?)
+        .ok_or_else(|| NodeSparkError::not_installed(version))?;
+    let version_dir = dirs.versions_dir.join(&version);
+
+    let packages = match npm_globals::list(&version_dir) {
+        Ok(packages) => packages,
+        Err(e) => {
+            say!("{} could not read global packages of Node.js {}: {}", "Warning:".warning(), version, e);
+            Vec::new()
+        }
+    };
+
+    let backup = dirs.config_dir.join(format!("globals-{}.json", version));
+    if !packages.is_empty() {
+        say!("Backing up {} global package(s) to {}", packages.len(), backup.display());
+        fs::write(&backup, serde_json::to_string_pretty(&packages)?)?;
+    }
+
+    let arch = config::load_config()?.version_arch.get(&version)
+        .map(|arch| utils::normalize_arch(arch))
+        .transpose()?;
+    let options = InstallOptions { force: true, arch, ..Default::default() };
+    if let Err(e) = install::execute(Some(&version), None, false, &options) {
+        // A failed install puts the previous one back, globals included, so the backup is only needed if that failed too
+        if version_dir.exists() {
+            let _ = fs::remove_file(&backup);
+        } else if !packages.is_empty() {
+            keep_backup_hint(&backup, &version, &packages);
+        }
+        return Err(e);
+    }
+
+    if !packages.is_empty() {
+        say!("Restoring global packages: {}", packages.join(", "));
+        if let Err(e) = npm_globals::install(&version_dir, &packages) {
+            keep_backup_hint(&backup, &version, &packages);
+            return Err(e);
+        }
+        fs::remove_file(&backup)?;
+    }
+
+    say!("Reinstalled Node.js {}", version.success());
+    Ok(())
+}
+
+fn keep_backup_hint(backup: &Path, version: &str, packages: &[String]) {
+    say!("{} the global package list is kept in {}; once Node.js {} works again, restore it with: npm install -g {}",
+        "Warning:".warning(), backup.display(), version, packages.join(" "));
+}
@@ -1,31 +1,224 @@
 use anyhow::{Result, anyhow};
-use colored::Colorize;
+use std::collections::BTreeMap;
 use std::fs;
-use crate::config;
-use crate::utils;
+use crate::error::NodeSparkError;
+use crate::commands::{install, link};
+use crate::config::{self, Config, NodeSparkDirs};
+use crate::options::picker::{self, PickerItem};
+use crate::options::prompt;
+use crate::utils::{self, VersionSpec, receipt, shim};
+use crate::utils::channel::Channel;
+use crate::options::theme::Themed;
+use crate::say;
 
-pub fn execute(version: &str) -> Result<()> {
+pub fn execute(specs: &[String], force: bool) -> Result<()> {
     let dirs = config::get_dirs()?;
-    let config = config::load_config()?;
+    let mut config = config::load_config()?;
+    let installed = utils::installed_versions(&dirs.versions_dir)?;
     
-    let actual_version = utils::parse_version(version)?;
+    let (mut selected, needs_confirmation) = if specs.is_empty() {
+        match pick(&config, &installed)? {
+            Some(picked) => (picked, false),
+            None => {
+                say!("Nothing removed");
+                return Ok(());
+            }
+        }
+    } else {
+        let ranges = specs.iter().any(|spec| utils::parse_version(spec).is_err());
+        (select(specs, &config, &installed, force)?, specs.len() > 1 || ranges)
+    };
+    
+    utils::sort_versions_desc(&mut selected);
+    selected.dedup();
+    if selected.is_empty() {
+        say!("Nothing to remove");
+        return Ok(());
+    }
+    
+    if needs_confirmation {
+        say!("The following Node.js versions will be removed:");
+        for version in &selected {
+            say!("  {}", version);
+        }
+        if !prompt::confirm(&format!("Remove {} version(s)?", selected.len()))? {
+            say!("Nothing removed");
+            return Ok(());
+        }
+    }
+    
+    if config.active_version.as_ref().is_some_and(|active| selected.contains(active)) {
+        switch_away(&mut config, &installed, &selected)?;
+    }
     
-    let version_dir = dirs.versions_dir.join(&actual_version);
-    if !version_dir.exists() {
-        return Err(anyhow!("Node.js {} is not installed", actual_version));
+    let (linked, regular): (Vec<String>, Vec<String>) = selected.into_iter()
+        .partition(|version| config.linked_versions.contains_key(version));
+    for version in &regular {
+        remove_installed(&dirs, &mut config, version)?;
+        say!("Successfully removed Node.js {}", version.success());
     }
+    config::save_config(&config)?;
     
-    if let Some(ref active) = config.active_version {
-        if *active == actual_version {
-            return Err(anyhow!(
-                "Cannot remove the active Node.js version. Switch to another version first."
-            ));
+    for version in &linked {
+        link::unlink(version)?;
+    }
+    
+    Ok(())
+}
+
+fn select(specs: &[String], config: &Config, installed: &[String], force: bool) -> Result<Vec<String>> {
+    let mut selected: Vec<String> = Vec::new();
+    for spec in specs {
+        match utils::parse_version_spec(spec)? {
+            VersionSpec::Exact(version) => {
+                if !installed.contains(&version) {
+                    return Err(NodeSparkError::VersionNotFound(format!("Node.js {} is not installed", version)).into());
+                }
+                if config.active_version.as_ref() == Some(&version) && !force {
+                    return Err(anyhow!(
+                        "Cannot remove the active Node.js version. Switch to another version first, or use --force."
+                    ));
+                }
+                selected.push(version);
+            }
+            requirement => {
+                let matches: Vec<&String> = installed.iter().filter(|version| requirement.matches(version)).collect();
+                if matches.is_empty() {
+                    return Err(NodeSparkError::VersionNotFound(format!("No installed Node.js version matches {}", spec)).into());
+                }
+                for version in matches {
+                    if config.active_version.as_ref() == Some(version) && !force {
+                        say!("Keeping the active Node.js {} (matched {})", version.current(), spec);
+                    } else {
+                        selected.push(version.clone());
+                    }
+                }
+            }
         }
     }
     
-    fs::remove_dir_all(&version_dir)?;
+    Ok(selected)
+}
+
+fn pick(config: &Config, installed: &[String]) -> Result<Option<Vec<String>>> {
+    if installed.is_empty() {
+        say!("No versions installed");
+        return Ok(None);
+    }
+    
+    let items: Vec<PickerItem> = installed.iter().map(|version| {
+        let active = config.active_version.as_ref() == Some(version);
+        PickerItem {
+            label: version.clone(),
+            note: active.then(|| "(active, protected)".to_string()),
+            locked: active,
+        }
+    }).collect();
+    
+    let picked = picker::multi_select("Select Node.js versions to remove", &items)?;
+    Ok(picked.map(|indices| indices.into_iter().map(|i| installed[i].clone()).collect()))
+}
+
+fn switch_away(config: &mut Config, installed: &[String], removing: &[String]) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let from = config.active_version.take().unwrap_or_default();
+    let next = installed.iter()
+        .filter(|version| Channel::of(version) != Channel::Nightly)
+        .find(|version| !removing.contains(version));
+    
+    config.previous_version = None;
+    config.tracked_channel = None;
+    config.active_version = next.cloned();
+    config::save_config(config)?;
     
-    println!("Successfully removed Node.js {}", actual_version.green());
+    match next {
+        Some(next) => {
+            install::create_node_symlinks(next)?;
+            say!("Switched from Node.js {} to {}", from, next.success());
+        }
+        None => {
+            for name in ["node", "npm", "npx"] {
+                shim::remove(&dirs.bin_dir.join(name))?;
+            }
+            say!("No other Node.js version is installed; cleared the active version");
+        }
+    }
+    Ok(())
+}
+
+// Applies keep_per_major once `version` has become the newest installed patch of its major
+pub fn remove_superseded(version: &str) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let mut config = config::load_config()?;
+    
+    let keep = match config.keep_per_major {
+        Some(keep) => keep.max(1),
+        None => return Ok(()),
+    };
+    let major = match utils::major_version(version) {
+        Some(major) => major,
+        None => return Ok(()),
+    };
+    let installed = utils::installed_versions(&dirs.versions_dir)?;
+    let newest = installed.iter()
+        .filter(|installed| Channel::of(installed) == Channel::Release)
+        .find(|installed| utils::major_version(installed) == Some(major));
+    if newest.map(String::as_str) != Some(version) {
+        return Ok(());
+    }
+    
+    let superseded: Vec<String> = superseded_versions(&config, &installed, keep)
+        .into_iter()
+        .filter(|installed| utils::major_version(installed) == Some(major))
+        .filter(|installed| installed != version)
+        .collect();
+    
+    if superseded.is_empty() {
+        return Ok(());
+    }
+    
+    for old in &superseded {
+        remove_installed(&dirs, &mut config, old)?;
+        say!("Removed superseded Node.js {} (keep_per_major = {})", old.muted(), keep);
+    }
+    config::save_config(&config)?;
+    
+    Ok(())
+}
+
+pub fn superseded_versions(config: &Config, installed: &[String], keep: usize) -> Vec<String> {
+    let mut seen: BTreeMap<u64, usize> = BTreeMap::new();
+    installed.iter()
+        .filter(|version| Channel::of(version) == Channel::Release)
+        .filter(|version| {
+            let major = match utils::major_version(version) {
+                Some(major) => major,
+                None => return false,
+            };
+            let count = seen.entry(major).or_default();
+            *count += 1;
+            *count > keep
+        })
+        .filter(|version| config.active_version.as_deref() != Some(version.as_str()))
+        .filter(|version| !config.locked_versions.contains(*version))
+        .filter(|version| !config.linked_versions.contains_key(*version))
+        .cloned()
+        .collect()
+}
+
+pub fn remove_installed(dirs: &NodeSparkDirs, config: &mut Config, version: &str) -> Result<()> {
+    fs::remove_dir_all(dirs.versions_dir.join(version))?;
+    receipt::remove(dirs, version)?;
+    let headers_dir = dirs.headers_dir.join(version);
+    if headers_dir.exists() {
+        fs::remove_dir_all(headers_dir)?;
+    }
+    
+    if config.previous_version.as_deref() == Some(version) {
+        config.previous_version = None;
+    }
+    config.last_used.remove(version);
+    config.version_arch.remove(version);
     
     Ok(())
 }
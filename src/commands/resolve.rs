@@ -0,0 +1,89 @@
+use anyhow::{Result, anyhow};
+use std::env;
+use std::path::Path;
+use crate::error::NodeSparkError;
+use crate::commands::{daemon, r#use};
+use crate::config::{self, Config};
+use serde_json::json;
+use crate::options::{output, verbose};
+use crate::utils::{self, project_version};
+
+#[derive(Clone)]
+pub enum Resolution {
+    Installed { version: String, source: String },
+    Missing { spec: String, source: String },
+    Nothing,
+}
+
+impl Resolution {
+    pub fn encode(&self) -> String {
+        match self {
+            Resolution::Installed { version, source } => format!("ok\t{}\t{}", version, source),
+            Resolution::Missing { spec, source } => format!("missing\t{}\t{}", spec, source),
+            Resolution::Nothing => "none".to_string(),
+        }
+    }
+
+    pub fn decode(line: &str) -> Option<Self> {
+        let mut fields = line.trim_end().splitn(3, '\t');
+        match (fields.next()?, fields.next(), fields.next()) {
+            ("ok", Some(version), Some(source)) => Some(Resolution::Installed {
+                version: version.to_string(),
+                source: source.to_string(),
+            }),
+            ("missing", Some(spec), Some(source)) => Some(Resolution::Missing {
+                spec: spec.to_string(),
+                source: source.to_string(),
+            }),
+            ("none", None, None) => Some(Resolution::Nothing),
+            _ => None,
+        }
+    }
+}
+
+pub fn execute(dir: Option<&Path>, no_daemon: bool) -> Result<()> {
+    let dir = match dir {
+        Some(dir) => dir.to_path_buf(),
+        None => env::current_dir()?,
+    };
+
+    let resolution = match (no_daemon, daemon::query(&dir)) {
+        (false, Some(resolution)) => resolution,
+        _ => {
+            verbose::log("Resolver daemon not reachable, resolving directly");
+            let dirs = config::get_dirs()?;
+            let installed = utils::installed_versions(&dirs.versions_dir)?;
+            resolve(&dir, &config::load_config()?, &installed)?
+        }
+    };
+
+    match resolution {
+        Resolution::Installed { version, source } => {
+            if output::is_json() {
+                output::print_json(&json!({ "version": version, "source": source }))?;
+            } else {
+                println!("{}\t{}", version, source);
+            }
+            Ok(())
+        }
+        Resolution::Missing { spec, source } => {
+            Err(NodeSparkError::VersionNotFound(format!("Node.js {} required by {} is not installed", spec, source)).into())
+        }
+        Resolution::Nothing => Err(anyhow!("No project version file found and no active Node.js version")),
+    }
+}
+
+pub fn resolve(dir: &Path, config: &Config, installed: &[String]) -> Result<Resolution> {
+    if let Some(project) = project_version::find(dir)? {
+        let source = project.source.to_string_lossy().to_string();
+        return Ok(match r#use::resolve_installed_spec(&project.spec, installed) {
+            Some(version) => Resolution::Installed { version, source },
+            None => Resolution::Missing { spec: project.spec, source },
+        });
+    }
+
+    Ok(match config.active_version {
+        Some(ref version) => Resolution::Installed { version: version.clone(), source: "active".to_string() },
+        None => Resolution::Nothing,
+    })
+}
@@ -0,0 +1,89 @@
+use anyhow::{Result, anyhow};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::commands::{exec, install, r#use};
+use crate::config;
+use crate::error::NodeSparkError;
+use crate::options::verbose;
+use crate::utils::{self, project_version};
+
+pub fn execute(script: &str, env_files: &[PathBuf], args: &[String]) -> Result<()> {
+    let version = resolve_project_version()?;
+
+    let cwd = env::current_dir()?;
+    // A package.json script wins over a file or directory that happens to share its name
+    let mut command = if !has_package_script(&cwd, script) && is_script_file(script) {
+        verbose::log(&format!("Running {} with node", script));
+        vec!["node".to_string(), script.to_string()]
+    } else {
+        let package_manager = detect_package_manager(&cwd);
+        vec![package_manager.to_string(), "run".to_string(), script.to_string()]
+    };
+    command.extend(args.iter().cloned());
+
+    exec::run_under(&version, env_files, &command)
+}
+
+pub fn resolve_project_version() -> Result<String> {
+    resolve_project_version_with(config::load_config()?.auto_install)
+}
+
+pub fn resolve_project_version_with(install_missing: bool) -> Result<String> {
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+
+    let project = match project_version::find_in_current_dir()? {
+        Some(project) => project,
+        None => {
+            verbose::log("No project version file found, using the active version");
+            return config.active_version.ok_or_else(|| {
+                anyhow!("No project version file found and no active Node.js version. Use 'node-spark use <version>' first.")
+            });
+        }
+    };
+
+    verbose::log(&format!("Project requires Node.js {} ({})", project.spec, project.source.display()));
+
+    let installed = utils::installed_versions(&dirs.versions_dir)?;
+    if let Some(version) = r#use::resolve_installed_spec(&project.spec, &installed) {
+        return Ok(version);
+    }
+
+    if !install_missing {
+        return Err(NodeSparkError::VersionNotFound(format!(
+            "Node.js {} required by {} is not installed. Use 'node-spark install {}' first.",
+            project.spec, project.source.display(), project.spec
+        )).into());
+    }
+
+    install::ensure_installed(&project.spec)
+}
+
+fn has_package_script(dir: &Path, script: &str) -> bool {
+    fs::read_to_string(dir.join("package.json")).ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .is_some_and(|package| package["scripts"].get(script).is_some())
+}
+
+fn is_script_file(script: &str) -> bool {
+    let path = Path::new(script);
+    let has_script_extension = path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| matches!(ext, "js" | "mjs" | "cjs"))
+        .unwrap_or(false);
+
+    has_script_extension || path.is_file()
+}
+
+fn detect_package_manager(dir: &Path) -> &'static str {
+    if dir.join("pnpm-lock.yaml").exists() {
+        "pnpm"
+    } else if dir.join("yarn.lock").exists() {
+        "yarn"
+    } else if cfg!(target_os = "windows") {
+        "npm.cmd"
+    } else {
+        "npm"
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::{Result, anyhow};
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+use crate::commands::env::Shell;
+use crate::config::{self, NodeSparkDirs};
+use crate::options::{symbols, verbose};
+use crate::options::theme::Themed;
+use crate::utils;
+use crate::say;
+
+const MARKER: &str = "# Added by node-spark setup";
+
+pub fn execute(shell: Option<Shell>, print_only: bool) -> Result<()> {
+    verbose::log("Executing setup command");
+    let shell = shell.map_or_else(Shell::detect, Ok)?;
+    let dirs = config::get_dirs()?;
+    let bin_dir = dirs.bin_dir.to_string_lossy().to_string();
+
+    if cfg!(target_os = "windows") && matches!(shell, Shell::Powershell | Shell::Cmd) {
+        return setup_windows(&bin_dir, print_only);
+    }
+
+    let line = path_line(shell, &bin_dir)?;
+    let rc_file = rc_file(shell)?;
+
+    if print_only {
+        say!("Add this line to {}:", rc_file.display());
+        println!("{}", line);
+        return Ok(());
+    }
+
+    if fs::read_to_string(&rc_file).is_ok_and(|content| content.contains(&line)) {
+        say!("{} {} already adds {} to PATH", symbols::ok().success(), rc_file.display(), bin_dir);
+        return Ok(());
+    }
+
+    if let Some(parent) = rc_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(&rc_file)?;
+    writeln!(file, "\n{}\n{}", MARKER, line)?;
+
+    say!("{} Added {} to PATH in {}", symbols::ok().success(), bin_dir.info(), rc_file.display());
+    if !utils::is_on_path(&dirs.bin_dir) {
+        say!("Open a new terminal (or source {}) for the change to take effect", rc_file.display());
+    }
+    Ok(())
+}
+
+fn path_line(shell: Shell, bin_dir: &str) -> Result<String> {
+    match shell {
+        Shell::Bash | Shell::Zsh => Ok(format!("export PATH={}:\"$PATH\"", shell.quote(bin_dir))),
+        Shell::Fish => Ok(format!("fish_add_path {}", shell.quote(bin_dir))),
+        Shell::Powershell => Ok(format!("$env:PATH = {} + [IO.Path]::PathSeparator + $env:PATH", shell.quote(bin_dir))),
+        Shell::Cmd => Err(anyhow!("cmd has no startup file; run 'node-spark setup --shell powershell' on Windows")),
+    }
+}
+
+fn rc_file(shell: Shell) -> Result<PathBuf> {
+    let home = directories::BaseDirs::new()
+        .map(|base| base.home_dir().to_path_buf())
+        .ok_or_else(|| anyhow!("Failed to determine the home directory"))?;
+
+    Ok(match shell {
+        Shell::Bash if cfg!(target_os = "macos") => home.join(".bash_profile"),
+        Shell::Bash => home.join(".bashrc"),
+        Shell::Zsh => env::var_os("ZDOTDIR").map(PathBuf::from).unwrap_or(home).join(".zshrc"),
+        Shell::Fish => home.join(".config").join("fish").join("config.fish"),
+        Shell::Powershell => home.join(".config").join("powershell").join("Microsoft.PowerShell_profile.ps1"),
+        Shell::Cmd => return Err(anyhow!("cmd has no startup file")),
+    })
+}
+
+fn setup_windows(bin_dir: &str, print_only: bool) -> Result<()> {
+    let quoted = Shell::Powershell.quote(bin_dir);
+    let script = format!(
+        "$path = [Environment]::GetEnvironmentVariable('Path', 'User'); \
+         if (-not (($path -split ';') -contains {quoted})) {{ \
+         [Environment]::SetEnvironmentVariable('Path', {quoted} + ';' + $path, 'User') }}"
+    );
+
+    if print_only {
+        say!("Run this in PowerShell to add {} to your user PATH:", bin_dir);
+        println!("{}", script);
+        return Ok(());
+    }
+
+    update_user_path(&script)?;
+    say!("{} Added {} to your user PATH", symbols::ok().success(), bin_dir.info());
+    say!("Open a new terminal for the change to take effect");
+    Ok(())
+}
+
+fn update_user_path(script: &str) -> Result<()> {
+    verbose::log("Updating the user PATH in the registry");
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", script])
+        .output()
+        .map_err(|e| anyhow!("Failed to run powershell: {}", e))?;
+
+    if !output.status.success() {
+        return Err(anyhow!("Failed to update the user PATH: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
+pub fn warn_if_not_on_path(dirs: &NodeSparkDirs) {
+    if !utils::is_on_path(&dirs.bin_dir) {
+        say!(
+            "{} {} is not on PATH, so node, npm and npx will not use this version. Run 'node-spark setup' to fix that.",
+            "Warning:".warning(),
+            dirs.bin_dir.display()
+        );
+        return;
+    }
+
+    if dirs.bin_dir.join("node").symlink_metadata().is_err() {
+        return;
+    }
+    // The init hook puts versions/<v>/bin ahead of the bin directory, which is still ours
+    let ours = |dir: &PathBuf| *dir == dirs.bin_dir || dir.starts_with(&dirs.versions_dir);
+    if let Some(shadowing) = utils::first_on_path("node").filter(|dir| !ours(dir)) {
+        say!(
+            "{} node resolves to {} first, ahead of {}. Move the node-spark bin directory earlier in PATH or remove the other installation.",
+            "Warning:".warning(),
+            shadowing.display(),
+            dirs.bin_dir.display()
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use anyhow::Result;
+use std::env;
+use crate::commands::{init, r#use};
+use crate::config;
+use serde_json::json;
+use crate::options::{output, symbols, verbose};
+use crate::utils::{self, archive_cache, disk, download, project_version};
+use crate::utils::channel::Channel;
+use crate::utils::schedule::{self, Phase};
+use crate::options::theme::Themed;
+use crate::say;
+
+pub fn execute() -> Result<()> {
+    verbose::log("Executing status command");
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+    let installed = utils::installed_versions(&dirs.versions_dir)?;
+    let releases: Vec<String> = installed.iter()
+        .filter(|version| Channel::of(version) != Channel::Nightly)
+        .cloned()
+        .collect();
+
+    if output::is_json() {
+        let project = project_version::find_in_current_dir()?;
+        return output::print_json(&json!({
+            "active_version": config.active_version,
+            "tracked_channel": config.tracked_channel,
+            "project": project.map(|project| json!({
+                "spec": project.spec,
+                "source": project.source,
+                "matches": config.active_version.as_ref()
+                    .is_some_and(|active| project_matches(&project.spec, active, &installed)),
+            })),
+            "installed": installed,
+            "installed_bytes": disk::dir_size(&dirs.versions_dir),
+            "cached_archives": archive_cache::cached_versions(&dirs)?,
+            "cache_bytes": archive_cache::total_size(&dirs),
+            "bin_on_path": utils::is_on_path(&dirs.bin_dir),
+            "shell_hook": env::var(init::HOOK_VAR).ok(),
+            "updates": update_findings(&releases).map(|findings| json!({
+                "outdated": findings.outdated.iter()
+                    .map(|(version, latest)| json!({ "version": version, "latest": latest }))
+                    .collect::<Vec<_>>(),
+                "missing_security": findings.insecure.iter()
+                    .map(|(version, fixed_in)| json!({ "version": version, "fixed_in": fixed_in }))
+                    .collect::<Vec<_>>(),
+            })),
+            "end_of_life": end_of_life(&releases),
+        }));
+    }
+
+    say!("{}", "node-spark status".brand());
+
+    match config.active_version {
+        Some(ref active) => match config.tracked_channel {
+            Some(ref channel) => say!("  Active version:    {} (tracking {})", active.current(), channel),
+            None => say!("  Active version:    {}", active.current()),
+        },
+        None => say!("  Active version:    {}", "none".warning()),
+    }
+
+    match project_version::find_in_current_dir()? {
+        Some(project) => {
+            let source = project.source.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let matches = config.active_version.as_ref()
+                .is_some_and(|active| project_matches(&project.spec, active, &installed));
+
+            if matches {
+                say!("  Project expects:   {} ({}) {}", project.spec, source, "matches".success());
+            } else {
+                say!("  Project expects:   {} ({}) {}", project.spec, source, "mismatch".error());
+            }
+        }
+        None => say!("  Project expects:   {}", "no version file in this directory or its parents".muted()),
+    }
+
+    let total_size = disk::dir_size(&dirs.versions_dir);
+    say!(
+        "  Installed:         {} version(s), {}",
+        installed.len(),
+        disk::format_size(total_size)
+    );
+
+    let cached = archive_cache::cached_versions(&dirs)?;
+    let cache_limit = match config.cache_max_mb {
+        Some(max_mb) => format!(" of {}", disk::format_size(archive_cache::max_bytes(max_mb))),
+        None => String::new(),
+    };
+    say!(
+        "  Archive cache:     {} archive(s), {}{}",
+        cached.len(),
+        disk::format_size(archive_cache::total_size(&dirs)),
+        cache_limit
+    );
+
+    match env::var(init::HOOK_VAR) {
+        Ok(shell) => say!("  Shell integration: {}", format!("init hook loaded ({})", shell).success()),
+        Err(_) if utils::is_on_path(&dirs.bin_dir) => say!(
+            "  Shell integration: {} {}",
+            "bin directory on PATH, no init hook".warning(),
+            "(automatic switching is off, see 'node-spark init')".muted()
+        ),
+        Err(_) => say!(
+            "  Shell integration: {} ({})",
+            "bin directory is not on PATH".error(),
+            dirs.bin_dir.display()
+        ),
+    }
+
+    print_updates(&releases);
+    print_end_of_life(&releases);
+
+    Ok(())
+}
+
+// Channel specs such as "lts/*" or "node" name whichever installed version the channel resolves to, not a range
+fn project_matches(spec: &str, active: &str, installed: &[String]) -> bool {
+    if download::is_alias(spec) {
+        r#use::resolve_installed_spec(spec, installed).as_deref() == Some(active)
+    } else {
+        utils::version_satisfies(spec, active)
+    }
+}
+
+struct UpdateFindings {
+    // (installed, newest in its major line)
+    outdated: Vec<(String, String)>,
+    // (installed, release that fixes it)
+    insecure: Vec<(String, String)>,
+}
+
+fn update_findings(installed: &[String]) -> Option<UpdateFindings> {
+    if installed.is_empty() {
+        return None;
+    }
+
+    let index = match download::get_remote_index() {
+        Ok(index) => index,
+        Err(e) => {
+            verbose::log(&format!("Failed to fetch remote versions: {}", e));
+            return None;
+        }
+    };
+
+    let mut findings = UpdateFindings { outdated: Vec::new(), insecure: Vec::new() };
+    for version in installed {
+        if let Some(newest) = download::newest_in_major(version, &index) {
+            if newest.version != *version && !installed.contains(&newest.version) {
+                findings.outdated.push((version.clone(), newest.version.clone()));
+            }
+        }
+
+        if let Some(fix) = download::missing_security_release(version, &index) {
+            if !installed.iter().any(|other| other == &fix.version) {
+                findings.insecure.push((version.clone(), fix.version.clone()));
+            }
+        }
+    }
+    Some(findings)
+}
+
+fn print_updates(installed: &[String]) {
+    if installed.is_empty() {
+        return;
+    }
+
+    let Some(findings) = update_findings(installed) else {
+        say!("  Updates:           {}", "unable to reach the Node.js download server".warning());
+        return;
+    };
+
+    if findings.outdated.is_empty() {
+        say!("  Updates:           {}", "all installed versions are up to date".success());
+    } else {
+        let outdated: Vec<String> = findings.outdated.iter()
+            .map(|(version, latest)| format!("{} {} {}", version, symbols::arrow(), latest))
+            .collect();
+        say!("  Updates:           {}", outdated.join(", ").warning());
+    }
+
+    if !findings.insecure.is_empty() {
+        let insecure: Vec<String> = findings.insecure.iter()
+            .map(|(version, fix)| format!("{} (fixed in {})", version, fix))
+            .collect();
+        say!("  Security:          {} {}", symbols::shield().error(),
+            format!("missing security releases: {}", insecure.join(", ")).error());
+    }
+}
+
+fn end_of_life(installed: &[String]) -> Vec<String> {
+    let Some(schedule) = schedule::load() else { return Vec::new() };
+    installed.iter()
+        .filter(|version| schedule.phase(version) == Some(Phase::EndOfLife))
+        .cloned()
+        .collect()
+}
+
+fn print_end_of_life(installed: &[String]) {
+    let ended = end_of_life(installed);
+    if !ended.is_empty() {
+        say!("  End of life:       {}", format!("{} no longer receive(s) security fixes", ended.join(", ")).error());
+    }
+}
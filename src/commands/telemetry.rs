@@ -0,0 +1,163 @@
+use anyhow::{Result, anyhow};
+use serde::{Serialize, Deserialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::config::{self, Config};
+use crate::utils::{self, http};
+use crate::options::theme::Themed;
+use crate::say;
+
+const LOCAL: &str = "local";
+const ON: &str = "on";
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Stats {
+    #[serde(default)]
+    since: u64,
+    #[serde(default)]
+    platform: String,
+    #[serde(default)]
+    commands: BTreeMap<String, u64>,
+    #[serde(default)]
+    errors: BTreeMap<String, u64>,
+}
+
+pub fn enable(local_only: bool, endpoint: Option<String>) -> Result<()> {
+    let mut config = config::load_config()?;
+
+    if let Some(endpoint) = endpoint {
+        reqwest::Url::parse(&endpoint).map_err(|e| anyhow!("Invalid endpoint {}: {}", endpoint, e))?;
+        config.telemetry_endpoint = Some(endpoint);
+    }
+
+    if !local_only && config.telemetry_endpoint.is_none() {
+        return Err(anyhow!("No telemetry endpoint configured. Pass --endpoint <URL>, or --local-only to only keep stats on this machine."));
+    }
+
+    config.telemetry = Some(if local_only { LOCAL } else { ON }.to_string());
+    config::save_config(&config)?;
+
+    if local_only {
+        say!("Usage statistics {} (local only, written to {})", "enabled".success(), stats_path()?.display());
+    } else {
+        say!("Usage statistics {} (sent to {})", "enabled".success(),
+            config.telemetry_endpoint.as_deref().unwrap_or_default());
+    }
+    Ok(())
+}
+
+pub fn disable() -> Result<()> {
+    let mut config = config::load_config()?;
+    config.telemetry = None;
+    config::save_config(&config)?;
+
+    say!("Usage statistics {}", "disabled".warning());
+    let path = stats_path()?;
+    if path.exists() {
+        say!("Previously recorded stats remain in {}; delete it to discard them.", path.display());
+    }
+    Ok(())
+}
+
+pub fn status() -> Result<()> {
+    let config = config::load_config()?;
+    let path = stats_path()?;
+
+    match config.telemetry.as_deref() {
+        Some(ON) => say!("Usage statistics: {} (sent to {})", "enabled".success(),
+            config.telemetry_endpoint.as_deref().unwrap_or("no endpoint")),
+        Some(LOCAL) => say!("Usage statistics: {} (local only)", "enabled".success()),
+        _ => say!("Usage statistics: {}", "disabled".warning()),
+    }
+
+    let stats = load_stats(&path);
+    if stats.commands.is_empty() {
+        return Ok(());
+    }
+
+    say!("Recorded in {} (since {}):", path.display(), utils::format_age(stats.since));
+    for (command, count) in &stats.commands {
+        say!("  {:<16} {}", command, count);
+    }
+    if !stats.errors.is_empty() {
+        say!("Errors:");
+        for (category, count) in &stats.errors {
+            say!("  {:<16} {}", category, count.to_string().error());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn record(config: Option<&Config>, command: &str, result: &Result<()>) {
+    let config = match config {
+        Some(config) if matches!(config.telemetry.as_deref(), Some(ON) | Some(LOCAL)) => config,
+        _ => return,
+    };
+    let path = match stats_path() {
+        Ok(path) => path,
+        Err(_) => return,
+    };
+
+    let platform = format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH);
+    let category = result.as_ref().err().map(error_category);
+
+    let mut stats = load_stats(&path);
+    if stats.since == 0 {
+        stats.since = utils::now_secs();
+    }
+    stats.platform = platform.clone();
+    *stats.commands.entry(command.to_string()).or_default() += 1;
+    if let Some(category) = category {
+        *stats.errors.entry(category.to_string()).or_default() += 1;
+    }
+    if let Ok(content) = serde_json::to_string_pretty(&stats) {
+        let _ = fs::write(&path, content);
+    }
+
+    if config.telemetry.as_deref() != Some(ON) {
+        return;
+    }
+    if let Some(ref endpoint) = config.telemetry_endpoint {
+        let payload = json!({
+            "command": command,
+            "success": category.is_none(),
+            "error_category": category,
+            "platform": platform,
+            "node_spark_version": env!("CARGO_PKG_VERSION"),
+        });
+        let _ = http::builder()
+            .and_then(|builder| Ok(builder.timeout(UPLOAD_TIMEOUT).build()?))
+            .and_then(|client| Ok(client.post(endpoint).json(&payload).send()?));
+    }
+}
+
+fn error_category(error: &anyhow::Error) -> &'static str {
+    for cause in error.chain() {
+        if cause.is::<reqwest::Error>() {
+            return "network";
+        }
+        if cause.is::<std::io::Error>() {
+            return "io";
+        }
+        if cause.is::<serde_json::Error>() {
+            return "parse";
+        }
+    }
+    "other"
+}
+
+fn stats_path() -> Result<PathBuf> {
+    Ok(config::get_dirs()?.config_dir.join("telemetry.json"))
+}
+
+fn load_stats(path: &Path) -> Stats {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
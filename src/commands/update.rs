@@ -1,11 +1,12 @@
 use anyhow::{Result, anyhow};
-use colored::Colorize;
 use std::process::Command;
 use crate::options::verbose;
+use crate::options::theme::Themed;
+use crate::say;
 
 pub fn execute() -> Result<()> {
     verbose::log("Executing update command");
-    println!("Checking for updates to node-spark...");
+    say!("Checking for updates to node-spark...");
     
     let cargo_cmd = if cfg!(target_os = "windows") {
         "cargo.exe"
@@ -22,7 +23,7 @@ pub fn execute() -> Result<()> {
         }
     }
 
-    println!("Updating node-spark to the latest version...");
+    say!("Updating node-spark to the latest version...");
     
     let output = Command::new(cargo_cmd)
         .args(["install", "--force", "node-spark"])
@@ -34,11 +35,11 @@ pub fn execute() -> Result<()> {
         return Err(anyhow!("Failed to update node-spark: {}", stderr));
     }
 
-    println!("{}", "node-spark updated successfully!".green());
+    say!("{}", "node-spark updated successfully!".success());
     
     if let Err(e) = crate::create_alias() {
         verbose::log(&format!("Failed to create alias: {}", e));
-        println!("Note: Failed to create 'nsk' alias, but node-spark was updated successfully.");
+        say!("Note: Failed to create 'nsk' alias, but node-spark was updated successfully.");
     }
     
     Ok(())
@@ -0,0 +1,108 @@
+use anyhow::{Result, anyhow};
+use std::collections::BTreeSet;
+use crate::commands::{install, r#use, remove};
+use crate::config;
+use crate::options::{symbols, verbose};
+use crate::options::theme::Themed;
+use crate::utils::{self, download, npm_globals};
+use crate::say;
+
+pub fn execute(target: Option<&str>, migrate_globals: bool, prune: bool) -> Result<()> {
+    verbose::log("Executing upgrade command");
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+    let installed: Vec<String> = utils::installed_releases(&dirs.versions_dir)?
+        .into_iter()
+        .filter(|version| !config.linked_versions.contains_key(version))
+        .collect();
+
+    let majors: BTreeSet<u64> = match target {
+        Some("all") => installed.iter().filter_map(|version| utils::major_version(version)).collect(),
+        Some(major) => BTreeSet::from([major.trim_start_matches('v').parse::<u64>()
+            .map_err(|_| anyhow!("Expected a major version number or 'all', got {}", major))?]),
+        None => {
+            let active = config.active_version.as_ref()
+                .ok_or_else(|| anyhow!("No active Node.js version. Give a major version or 'all'."))?;
+            BTreeSet::from([utils::major_version(active)
+                .ok_or_else(|| anyhow!("Cannot determine the major version of {}", active))?])
+        }
+    };
+
+    if majors.is_empty() {
+        say!("No installed versions to upgrade");
+        return Ok(());
+    }
+
+    let index = download::get_remote_index()?;
+    for major in majors.into_iter().rev() {
+        upgrade_major(major, &installed, &index, migrate_globals, prune)?;
+    }
+
+    Ok(())
+}
+
+fn upgrade_major(
+    major: u64,
+    installed: &[String],
+    index: &[download::RemoteVersion],
+    migrate_globals: bool,
+    prune: bool,
+) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let newest = index.iter()
+        .find(|remote| utils::major_version(&remote.version) == Some(major))
+        .map(|remote| remote.version.clone())
+        .ok_or_else(|| anyhow!("No Node.js {} releases found", major))?;
+
+    let previous: Vec<&String> = installed.iter()
+        .filter(|version| utils::major_version(version) == Some(major) && **version != newest)
+        .collect();
+
+    if installed.contains(&newest) && previous.is_empty() {
+        say!("Node.js {} is already the newest {}.x release", newest.success(), major);
+        return Ok(());
+    }
+
+    if !installed.contains(&newest) {
+        say!("Installing Node.js {}", newest.success());
+        install::install_version(&newest, &install::InstallOptions::default())?;
+    }
+
+    let config = config::load_config()?;
+    let active = config.active_version.clone();
+    let source = previous.iter()
+        .find(|version| active.as_ref() == Some(**version))
+        .or_else(|| previous.first());
+
+    if migrate_globals {
+        if let Some(source) = source {
+            let packages = npm_globals::list(&dirs.versions_dir.join(source))?;
+            if !packages.is_empty() {
+                say!("Migrating {} global package(s) from {} {} {}", packages.len(), source, symbols::arrow(), newest);
+                npm_globals::install(&dirs.versions_dir.join(&newest), &packages)?;
+            }
+        }
+    }
+
+    if active.as_ref().is_some_and(|active| previous.contains(&active)) {
+        r#use::execute(Some(&newest), false, false)?;
+    }
+
+    if prune {
+        let mut config = config::load_config()?;
+        // use and install may already have removed some of them under keep_per_major
+        let still_installed = utils::installed_versions(&dirs.versions_dir)?;
+        for old in previous.iter().filter(|old| still_installed.contains(old)) {
+            if config.active_version.as_ref() == Some(*old) || config.locked_versions.contains(*old) {
+                say!("Keeping Node.js {} ({})", old, "active or locked".muted());
+                continue;
+            }
+            remove::remove_installed(&dirs, &mut config, old)?;
+            say!("Removed superseded Node.js {}", old.muted());
+        }
+        config::save_config(&config)?;
+    }
+
+    say!("Node.js {}.x upgraded to {}", major, newest.success());
+    Ok(())
+}
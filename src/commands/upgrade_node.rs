@@ -0,0 +1,51 @@
+use anyhow::{Result, anyhow};
+use crate::commands::{install, remove, r#use};
+use crate::config;
+use crate::options::{prompt, verbose};
+use crate::utils::{self, download};
+use crate::options::theme::Themed;
+use crate::say;
+
+pub fn execute(lts: bool) -> Result<()> {
+    verbose::log("Executing upgrade-node command");
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+
+    let active = config.active_version.clone()
+        .ok_or_else(|| anyhow!("No active Node.js version. Use 'node-spark use <version>' first."))?;
+    let major = utils::major_version(&active)
+        .ok_or_else(|| anyhow!("Cannot determine the major version of {}", active))?;
+
+    say!("Checking for newer Node.js {} releases...", major);
+    let index = download::get_remote_index()?;
+    let newest = download::newest_in_major(&active, &index)
+        .ok_or_else(|| anyhow!("No Node.js {} releases found", major))?;
+    // --lts only keeps LTS lines patched; it never moves a Current line or jumps to another major
+    if lts && newest.lts.is_none() {
+        say!("Node.js {}.x is not an LTS line, leaving {} alone (--lts)", major, active.success());
+        return Ok(());
+    }
+    let newest = &newest.version;
+
+    if *newest == active {
+        say!("Node.js {} is already the newest {}.x release", active.success(), major);
+        return Ok(());
+    }
+
+    if !prompt::confirm(&format!("Upgrade Node.js {} to {}?", active, newest))? {
+        say!("Staying on Node.js {}", active.success());
+        return Ok(());
+    }
+
+    if !dirs.versions_dir.join(newest).exists() {
+        say!("Installing Node.js {}", newest.success());
+        install::install_version(newest, &install::InstallOptions::default())?;
+    }
+
+    if config.tracked_channel.is_some() {
+        r#use::refresh_tracked_channel()?;
+    } else {
+        r#use::execute(Some(newest), false, false)?;
+    }
+    remove::remove_superseded(newest)
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use serde_json::json;
+use crate::error::NodeSparkError;
+use crate::options::output;
+use crate::utils::{self, download, platform::Platform};
+
+pub fn execute(spec: &str, target_os: Option<&str>, target_arch: Option<&str>) -> Result<()> {
+    let host = Platform::host();
+    let platform = Platform {
+        os: target_os.map(utils::normalize_os).transpose()?.unwrap_or(host.os),
+        ..host.with_arch(target_arch.map(utils::normalize_arch).transpose()?)
+    };
+
+    let index = download::index_for(spec)?;
+    let version = download::resolve_remote(spec, &index)
+        .ok_or_else(|| NodeSparkError::VersionNotFound(format!("No Node.js release matches {}", spec)))?;
+
+    // Pick the archive the same way install does, so musl hosts and .tar.xz releases get the real file
+    let shasums_url = platform.shasums_url(&version);
+    let shasums = download::fetch_text(&shasums_url)?;
+    let (file_name, checksum) = platform.listed_artifact(&version, &shasums).ok_or_else(|| {
+        NodeSparkError::VersionNotFound(format!("Node.js {} has no build for {} in {}", version, platform.name(), shasums_url))
+    })?;
+
+    let url = platform.file_url(&version, &file_name);
+    if output::is_json() {
+        output::print_json(&json!({ "version": version, "url": url, "file": file_name, "sha256": checksum }))?;
+    } else {
+        println!("{}", url);
+        println!("{}  {}", checksum, file_name);
+    }
+
+    Ok(())
+}
@@ -1,27 +1,197 @@
 use anyhow::{Result, anyhow};
-use colored::Colorize;
-use crate::config;
-use crate::commands::install::create_node_symlinks;
-use crate::utils;
+use crate::error::NodeSparkError;
+use crate::config::{self, NodeSparkDirs};
+use crate::commands::install::{self, create_node_symlinks};
+use crate::commands::setup;
+use crate::options::{events, prompt, symbols};
+use crate::options::picker::{self, PickerItem};
+use serde_json::json;
+use crate::utils::{self, download, project_version};
+use crate::utils::channel::Channel;
+use crate::options::theme::Themed;
+use crate::say;
 
-pub fn execute(version: &str) -> Result<()> {
+pub fn execute(version: Option<&str>, track: bool, install: bool) -> Result<()> {
     let dirs = config::get_dirs()?;
+    let mut config = config::load_config()?;
+    let auto_install = install || config.auto_install;
     
-    let actual_version = utils::parse_version(version)?;
+    let version = match version {
+        Some(version) => version.to_string(),
+        None => {
+            let project = project_version::find_in_current_dir()?
+                .ok_or_else(|| anyhow!("No version given and no .nvmrc, .node-version or package.json engines.node found in this directory or its parents"))?;
+            say!("Using Node.js {} from {}", project.spec, project.source.display());
+            let spec = project.spec.clone();
+            let installed = utils::installed_versions(&dirs.versions_dir)?;
+            if download::is_alias(&spec) {
+                spec
+            } else {
+                match utils::resolve_spec(&spec, &installed) {
+                    Some(version) => version,
+                    None if auto_install => spec,
+                    None => return Err(NodeSparkError::VersionNotFound(format!(
+                        "Node.js {} required by {} is not installed. Use 'node-spark install' or 'node-spark use --install' first.",
+                        project.spec, project.source.display()
+                    )).into()),
+                }
+            }
+        }
+    };
+    let version = version.as_str();
+    let channel = download::is_alias(version).then(|| version.to_lowercase());
     
-    let version_dir = dirs.versions_dir.join(&actual_version);
-    if !version_dir.exists() {
-        return Err(anyhow!("Node.js {} is not installed. Use 'node-spark install {}' first.",
-                            actual_version, actual_version));
+    if track && channel.is_none() {
+        return Err(anyhow!("--track only works with a channel: lts, lts/<codename> or latest"));
     }
     
-    let mut config = config::load_config()?;
+    let resolved = if version == "-" {
+        config.previous_version.clone()
+            .ok_or_else(|| anyhow!("No previous Node.js version to switch back to"))
+    } else if let Some(ref channel) = channel {
+        resolve_channel(&dirs, channel)
+    } else {
+        utils::resolve_spec(version, &utils::installed_versions(&dirs.versions_dir)?)
+            .ok_or_else(|| NodeSparkError::not_installed(version).into())
+    };
+    
+    let actual_version = match resolved {
+        Ok(resolved) if dirs.versions_dir.join(&resolved).exists() => resolved,
+        _ if auto_install && version != "-" => install::ensure_installed(version)?,
+        Ok(resolved) => return Err(NodeSparkError::VersionNotFound(format!("Node.js {} is not installed. Use 'node-spark install {}' or 'node-spark use --install {}'.",
+                                           resolved, resolved, version)).into()),
+        Err(e) => return Err(e),
+    };
+    
+    if let Some(ref current) = config.active_version {
+        if !confirm_major_jump(&dirs, current, &actual_version)? {
+            say!("Staying on Node.js {}", current.success());
+            return Ok(());
+        }
+    }
+
+    if config.active_version.as_deref() != Some(actual_version.as_str()) {
+        config.previous_version = config.active_version.take();
+    }
     config.active_version = Some(actual_version.clone());
+    config.last_used.insert(actual_version.clone(), utils::now_secs());
+    config.tracked_channel = if track { channel.clone() } else { None };
     config::save_config(&config)?;
     
     create_node_symlinks(&actual_version)?;
+    events::emit("version_activated", json!({ "version": actual_version }));
     
-    println!("Now using Node.js {}", actual_version.green());
+    say!("Now using Node.js {}", actual_version.success());
+    setup::warn_if_not_on_path(&dirs);
+    if let Some(channel) = config.tracked_channel {
+        say!("Tracking {}: the newest installed {} release is activated after each install", channel, channel);
+    }
     
     Ok(())
 }
+
+pub fn pick_version() -> Result<Option<String>> {
+    let dirs = config::get_dirs()?;
+    let config = config::load_config()?;
+    let installed = utils::installed_versions(&dirs.versions_dir)?;
+    let index = download::cached_remote_index().unwrap_or_default();
+    
+    let items: Vec<PickerItem> = installed.iter().map(|version| {
+        let mut notes = Vec::new();
+        if config.active_version.as_ref() == Some(version) {
+            notes.push("current".to_string());
+        }
+        if Channel::of(version) == Channel::Nightly {
+            notes.push("nightly".to_string());
+        }
+        if let Some(codename) = index.iter().find(|remote| &remote.version == version).and_then(|remote| remote.lts.as_ref()) {
+            notes.push(format!("LTS {}", codename));
+        }
+        PickerItem {
+            label: version.clone(),
+            note: (!notes.is_empty()).then(|| format!("({})", notes.join(", "))),
+            locked: false,
+        }
+    }).collect();
+    
+    let picked = picker::select("Select a Node.js version to use", &items)?;
+    Ok(picked.map(|i| installed[i].clone()))
+}
+
+pub fn resolve_channel(dirs: &NodeSparkDirs, channel: &str) -> Result<String> {
+    let installed = utils::installed_versions(&dirs.versions_dir)?;
+    Ok(channel_version(channel, &installed).ok_or_else(|| NodeSparkError::VersionNotFound(format!(
+        "No installed Node.js version matches {}. Use 'node-spark install {}' first.", channel, channel
+    )))?)
+}
+
+// Resolves a project or command-line spec against the installed versions, channels and codenames included
+pub fn resolve_installed_spec(spec: &str, installed: &[String]) -> Option<String> {
+    if download::is_alias(spec) {
+        channel_version(spec, installed)
+    } else {
+        utils::resolve_spec(spec, installed)
+    }
+}
+
+// The cached index answers most lookups, so the shell hooks stay off the network; it is only refreshed on a miss
+fn channel_version(channel: &str, installed: &[String]) -> Option<String> {
+    let kind = Channel::for_spec(channel);
+    download::installed_in_channel(channel, installed, &download::cached_channel_index(kind).unwrap_or_default())
+        .or_else(|| download::get_channel_index(kind).ok()
+            .and_then(|index| download::installed_in_channel(channel, installed, &index)))
+}
+
+pub fn refresh_tracked_channel() -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let mut config = config::load_config()?;
+    let channel = match config.tracked_channel.clone() {
+        Some(channel) => channel,
+        None => return Ok(()),
+    };
+    
+    let newest = resolve_channel(&dirs, &channel)?;
+    if config.active_version.as_deref() == Some(newest.as_str()) {
+        return Ok(());
+    }
+    if let Some(ref current) = config.active_version {
+        if !confirm_major_jump(&dirs, current, &newest)? {
+            say!("Tracking {}: staying on Node.js {}", channel, current.success());
+            return Ok(());
+        }
+    }
+    
+    config.previous_version = config.active_version.take();
+    config.active_version = Some(newest.clone());
+    config.last_used.insert(newest.clone(), utils::now_secs());
+    config::save_config(&config)?;
+    
+    create_node_symlinks(&newest)?;
+    events::emit("version_activated", json!({ "version": newest, "channel": channel }));
+    
+    say!("Tracking {}: now using Node.js {}", channel, newest.success());
+    Ok(())
+}
+
+pub fn confirm_major_jump(dirs: &NodeSparkDirs, from: &str, to: &str) -> Result<bool> {
+    let (from_major, to_major) = match (utils::major_version(from), utils::major_version(to)) {
+        (Some(from_major), Some(to_major)) if from_major != to_major => (from_major, to_major),
+        _ => return Ok(true),
+    };
+
+    let npm_of = |version: &str| {
+        utils::installed_npm_version(&dirs.versions_dir.join(version))
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+
+    say!("{}", "Switching across major versions:".warning());
+    say!("  Node.js: {} {} {}", from, symbols::arrow(), to.success());
+    say!("  Major:   {} {} {}", from_major, symbols::arrow(), to_major.to_string().success());
+    say!("  npm:     {} {} {}", npm_of(from), symbols::arrow(), npm_of(to).success());
+    say!(
+        "  Breaking changes: https://github.com/nodejs/node/blob/main/doc/changelogs/CHANGELOG_V{}.md",
+        to_major
+    );
+
+    prompt::confirm(&format!("Switch to Node.js {}?", to))
+}
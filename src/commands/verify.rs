@@ -0,0 +1,97 @@
+use anyhow::{Result, anyhow};
+use crate::error::NodeSparkError;
+use crate::config::{self, NodeSparkDirs};
+use crate::options::{symbols, verbose};
+use crate::utils::{self, receipt};
+use crate::options::theme::Themed;
+use crate::say;
+
+pub fn execute(version: Option<&str>, deep: bool) -> Result<()> {
+    let dirs = config::get_dirs()?;
+
+    let versions = match version {
+        Some(version) => vec![utils::parse_version(version)?],
+        None => utils::installed_versions(&dirs.versions_dir)?,
+    };
+
+    if versions.is_empty() {
+        say!("No versions installed");
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for version in &versions {
+        if !verify_version(&dirs, version, deep)? {
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        return Err(anyhow!("{} version(s) failed verification", failed));
+    }
+
+    Ok(())
+}
+
+fn verify_version(dirs: &NodeSparkDirs, version: &str, deep: bool) -> Result<bool> {
+    let version_dir = dirs.versions_dir.join(version);
+    if !version_dir.exists() {
+        return Err(NodeSparkError::VersionNotFound(format!("Node.js {} is not installed", version)).into());
+    }
+
+    let node_binary = if cfg!(target_os = "windows") {
+        version_dir.join("node.exe")
+    } else {
+        utils::version_bin_dir(&version_dir).join("node")
+    };
+    if !node_binary.exists() {
+        say!("{} {}: node binary missing ({})", symbols::fail().error(), version, node_binary.display());
+        return Ok(false);
+    }
+
+    let receipt = match receipt::load(dirs, version)? {
+        Some(receipt) => receipt,
+        None => {
+            say!("{} {}: no install receipt, reinstall to enable verification", symbols::skip().warning(), version);
+            return Ok(true);
+        }
+    };
+
+    let mut missing = Vec::new();
+    let mut modified = Vec::new();
+
+    for (relative, expected) in &receipt.files {
+        let path = version_dir.join(relative);
+        if path.symlink_metadata().is_err() {
+            missing.push(relative);
+            continue;
+        }
+
+        if deep && receipt::hash_entry(&path)? != *expected {
+            modified.push(relative);
+        }
+    }
+
+    for relative in &missing {
+        verbose::log(&format!("{}: missing {}", version, relative));
+    }
+    for relative in &modified {
+        verbose::log(&format!("{}: modified {}", version, relative));
+    }
+
+    if missing.is_empty() && modified.is_empty() {
+        let mode = if deep { "all file hashes match" } else { "all files present" };
+        say!("{} {}: {} ({} files)", symbols::ok().success(), version, mode, receipt.files.len());
+        return Ok(true);
+    }
+
+    say!(
+        "{} {}: {} missing, {} modified file(s)",
+        symbols::fail().error(), version, missing.len(), modified.len()
+    );
+    for relative in missing.iter().chain(modified.iter()).take(10) {
+        say!("{}{}", symbols::marker_indent(), relative);
+    }
+
+    Ok(false)
+}
@@ -0,0 +1,59 @@
+use anyhow::{Result, anyhow};
+use crate::error::NodeSparkError;
+use crate::config;
+use crate::commands::install::create_node_symlinks;
+use crate::utils;
+use crate::options::theme::Themed;
+use crate::say;
+
+pub fn execute(version: &str, vars: &[String], unset: &[String]) -> Result<()> {
+    let dirs = config::get_dirs()?;
+    let actual_version = utils::parse_version(version)?;
+
+    if !dirs.versions_dir.join(&actual_version).exists() {
+        return Err(NodeSparkError::VersionNotFound(format!("Node.js {} is not installed", actual_version)).into());
+    }
+
+    let mut config = config::load_config()?;
+
+    if vars.is_empty() && unset.is_empty() {
+        match config.version_env.get(&actual_version).filter(|env| !env.is_empty()) {
+            Some(env) => {
+                say!("Environment for Node.js {}:", actual_version.success());
+                for (key, value) in env {
+                    say!("  {}={}", key.emphasis(), value);
+                }
+            }
+            None => say!("No environment variables set for Node.js {}", actual_version),
+        }
+        return Ok(());
+    }
+
+    let env = config.version_env.entry(actual_version.clone()).or_default();
+
+    for assignment in vars {
+        let (key, value) = assignment.split_once('=')
+            .filter(|(key, _)| !key.is_empty())
+            .ok_or_else(|| anyhow!("Invalid assignment '{}', expected KEY=VALUE", assignment))?;
+        env.insert(key.to_string(), value.to_string());
+        say!("Set {} for Node.js {}", key.emphasis(), actual_version.success());
+    }
+
+    for key in unset {
+        if env.remove(key).is_some() {
+            say!("Unset {} for Node.js {}", key.emphasis(), actual_version.success());
+        }
+    }
+
+    if env.is_empty() {
+        config.version_env.remove(&actual_version);
+    }
+
+    config::save_config(&config)?;
+
+    if config.active_version.as_deref() == Some(actual_version.as_str()) {
+        create_node_symlinks(&actual_version)?;
+    }
+
+    Ok(())
+}
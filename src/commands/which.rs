@@ -0,0 +1,32 @@
+use anyhow::{Result, anyhow};
+use crate::error::NodeSparkError;
+use crate::commands::r#use;
+use serde_json::json;
+use crate::config;
+use crate::options::output;
+use crate::utils::{self, download};
+
+pub fn execute(version: Option<&str>) -> Result<()> {
+    let dirs = config::get_dirs()?;
+
+    let version = match version {
+        Some(spec) if download::is_alias(spec) => r#use::resolve_channel(&dirs, &spec.to_lowercase())?,
+        Some(spec) => utils::resolve_spec(spec, &utils::installed_versions(&dirs.versions_dir)?)
+            .ok_or_else(|| NodeSparkError::not_installed(spec))?,
+        None => config::load_config()?.active_version
+            .ok_or_else(|| anyhow!("No active Node.js version. Pass a version or run 'node-spark use <version>' first."))?,
+    };
+
+    let node_name = if cfg!(target_os = "windows") { "node.exe" } else { "node" };
+    let node_path = utils::version_bin_dir(&dirs.versions_dir.join(&version)).join(node_name);
+    if !node_path.exists() {
+        return Err(NodeSparkError::VersionNotFound(format!("Node.js {} is not installed. Use 'node-spark install {}' first.", version, version)).into());
+    }
+
+    if output::is_json() {
+        output::print_json(&json!({ "version": version, "path": node_path }))?;
+    } else {
+        println!("{}", node_path.display());
+    }
+    Ok(())
+}
@@ -1,26 +1,74 @@
 use directories::ProjectDirs;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
 use std::fs;
 use anyhow::{Result, Context};
 use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Config {
     pub active_version: Option<String>,
+    #[serde(default)]
+    pub previous_version: Option<String>,
+    #[serde(default)]
+    pub umask: Option<String>,
+    #[serde(default)]
+    pub version_env: BTreeMap<String, BTreeMap<String, String>>,
+    #[serde(default)]
+    pub auto_install: bool,
+    #[serde(default)]
+    pub auto_update: Option<String>,
+    #[serde(default)]
+    pub last_used: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub theme: Option<String>,
+    #[serde(default)]
+    pub colors: BTreeMap<String, String>,
+    #[serde(default)]
+    pub ascii: Option<bool>,
+    #[serde(default)]
+    pub linked_versions: BTreeMap<String, PathBuf>,
+    #[serde(default)]
+    pub tracked_channel: Option<String>,
+    #[serde(default)]
+    pub keep_per_major: Option<usize>,
+    #[serde(default)]
+    pub locked_versions: BTreeSet<String>,
+    #[serde(default)]
+    pub telemetry: Option<String>,
+    #[serde(default)]
+    pub telemetry_endpoint: Option<String>,
+    #[serde(default)]
+    pub verify_signatures: bool,
+    #[serde(default)]
+    pub proxy: Option<String>,
+    #[serde(default)]
+    pub dist_mirror: Option<String>,
+    #[serde(default)]
+    pub ca_bundle: Option<String>,
+    #[serde(default)]
+    pub insecure: bool,
+    #[serde(default)]
+    pub cache_max_mb: Option<u64>,
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    #[serde(default)]
+    pub workspace_roots: Vec<PathBuf>,
+    #[serde(default)]
+    pub schedule_url: Option<String>,
+    #[serde(default)]
+    pub unofficial_builds_url: Option<String>,
+    #[serde(default)]
+    pub version_arch: BTreeMap<String, String>,
 }
 
 pub struct NodeSparkDirs {
     pub config_dir: PathBuf,
     pub versions_dir: PathBuf,
     pub bin_dir: PathBuf,
-}
-
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            active_version: None,
-        }
-    }
+    pub receipts_dir: PathBuf,
+    pub headers_dir: PathBuf,
+    pub cache_dir: PathBuf,
 }
 
 pub fn get_dirs() -> Result<NodeSparkDirs> {
@@ -32,16 +80,25 @@ pub fn get_dirs() -> Result<NodeSparkDirs> {
     
     let versions_dir = data_dir.join("versions");
     let bin_dir = data_dir.join("bin");
+    let receipts_dir = data_dir.join("receipts");
+    let headers_dir = data_dir.join("headers");
+    let cache_dir = data_dir.join("cache");
     
     
     fs::create_dir_all(&config_dir)?;
     fs::create_dir_all(&versions_dir)?;
     fs::create_dir_all(&bin_dir)?;
+    fs::create_dir_all(&receipts_dir)?;
+    fs::create_dir_all(&headers_dir)?;
+    fs::create_dir_all(&cache_dir)?;
     
     Ok(NodeSparkDirs {
         config_dir,
         versions_dir,
         bin_dir,
+        receipts_dir,
+        headers_dir,
+        cache_dir,
     })
 }
 
@@ -69,3 +126,9 @@ pub fn save_config(config: &Config) -> Result<()> {
     
     Ok(())
 }
+
+pub fn record_usage(version: &str) -> Result<()> {
+    let mut config = load_config()?;
+    config.last_used.insert(version.to_string(), crate::utils::now_secs());
+    save_config(&config)
+}
@@ -0,0 +1,63 @@
+use std::io;
+use thiserror::Error;
+
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_VERSION_NOT_FOUND: i32 = 3;
+pub const EXIT_NETWORK: i32 = 4;
+pub const EXIT_PERMISSION: i32 = 5;
+pub const EXIT_VERIFICATION: i32 = 6;
+
+#[derive(Debug, Error)]
+pub enum NodeSparkError {
+    #[error("{0}")]
+    VersionNotFound(String),
+    #[error("{0}")]
+    Network(String),
+    #[error("{0}")]
+    Permission(String),
+    #[error("{0}")]
+    Verification(String),
+    // Leave with this exit code without printing an error, e.g. to pass on a child's status
+    #[error("exited with status {0}")]
+    Exit(i32),
+}
+
+impl NodeSparkError {
+    pub fn not_installed(spec: &str) -> NodeSparkError {
+        NodeSparkError::VersionNotFound(format!(
+            "No installed Node.js version matches {}. Use 'node-spark install {}' first.", spec, spec
+        ))
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            NodeSparkError::VersionNotFound(_) => EXIT_VERSION_NOT_FOUND,
+            NodeSparkError::Network(_) => EXIT_NETWORK,
+            NodeSparkError::Permission(_) => EXIT_PERMISSION,
+            NodeSparkError::Verification(_) => EXIT_VERIFICATION,
+            NodeSparkError::Exit(code) => *code,
+        }
+    }
+}
+
+pub fn is_silent(error: &anyhow::Error) -> bool {
+    matches!(error.downcast_ref::<NodeSparkError>(), Some(NodeSparkError::Exit(_)))
+}
+
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    for cause in error.chain() {
+        if let Some(error) = cause.downcast_ref::<NodeSparkError>() {
+            return error.exit_code();
+        }
+        if cause.is::<reqwest::Error>() {
+            return EXIT_NETWORK;
+        }
+        if let Some(error) = cause.downcast_ref::<io::Error>() {
+            if error.kind() == io::ErrorKind::PermissionDenied {
+                return EXIT_PERMISSION;
+            }
+        }
+    }
+
+    EXIT_FAILURE
+}
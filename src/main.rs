@@ -1,18 +1,27 @@
 mod commands;
 mod config;
+mod error;
 mod options;
 mod utils;
 
-use clap::{Parser, CommandFactory};
-use colored::Colorize;
+use clap::{CommandFactory, FromArgMatches};
+use serde_json::json;
+use std::time::Instant;
+use options::events;
+use options::theme::Themed;
 
 fn main() -> anyhow::Result<()> {
-    let cli = options::Cli::parse();
+    let matches = options::Cli::command().get_matches();
+    let cli = options::Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let command_name = matches.subcommand_name().unwrap_or("none").to_string();
 
     options::verbose::set_verbose(cli.verbose);
+    options::prompt::set_assume_yes(cli.yes);
+    events::set_format(cli.log_format);
+    options::output::set_format(cli.output);
 
     if cli.verbose && cli.version {
-        println!("Verbose mode: {}", "enabled".green());
+        println!("Verbose mode: {}", "enabled".success());
         options::version::show();
         return Ok(());
     }
@@ -22,20 +31,115 @@ fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
+    let config = config::load_config().ok();
+    options::symbols::init(cli.ascii, config.as_ref().and_then(|c| c.ascii));
+    options::tls::init(cli.ca_bundle.clone(), cli.insecure);
+
+    let started = Instant::now();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    events::emit("command_started", json!({ "args": args }));
+
+    let result = run(cli.command);
+
+    match result {
+        Ok(()) => events::emit("command_finished", json!({
+            "success": true,
+            "duration_ms": events::elapsed_ms(started),
+        })),
+        Err(ref e) => events::emit("command_finished", json!({
+            "success": false,
+            "duration_ms": events::elapsed_ms(started),
+            "error": format!("{:#}", e),
+            "exit_code": error::exit_code(e),
+        })),
+    }
+
+    commands::telemetry::record(config::load_config().ok().as_ref(), &command_name, &result);
+
+    if let Err(e) = result {
+        if !error::is_silent(&e) {
+            eprintln!("Error: {:?}", e);
+        }
+        std::process::exit(error::exit_code(&e));
+    }
+
+    Ok(())
+}
+
+fn alias(version: String) -> String {
+    utils::expand_alias(&version)
+}
+
+// A per-command --json behaves like --output json, so progress and human lines stay off stdout
+fn json_output(json: bool) -> bool {
+    if json {
+        options::output::set_format(options::output::OutputFormat::Json);
+    }
+    options::output::is_json()
+}
+
+fn run(command: Option<options::Commands>) -> anyhow::Result<()> {
     check_and_create_alias()?;
+    utils::interrupt::install_handler()?;
 
-    match cli.command {
-        Some(options::Commands::Install { version }) => {
-            commands::install::execute(&version)?;
+    match command {
+        Some(options::Commands::Install { versions, from_dir, with_headers, no_verify, verify_signatures, offline, force, flavor, arch, file, interactive }) => {
+            let install_options = commands::install::InstallOptions {
+                skip_checksum: no_verify,
+                verify_signatures,
+                offline,
+                force,
+                flavor,
+                arch: arch.as_deref().map(utils::normalize_arch).transpose()?,
+                file,
+                progress: None,
+            };
+            let versions: Vec<String> = match interactive {
+                true => commands::install::pick_version()?.into_iter().collect(),
+                false => versions.into_iter().map(alias).collect(),
+            };
+            if interactive && versions.is_empty() {
+                return Ok(());
+            }
+            if versions.len() > 1 {
+                if from_dir.is_some() || install_options.file.is_some() {
+                    return Err(anyhow::anyhow!("--from-dir and --file install a single version; give at most one version"));
+                }
+                commands::install::execute_many(&versions, with_headers, &install_options)?;
+            } else {
+                commands::install::execute(versions.first().map(String::as_str), from_dir.as_deref(), with_headers, &install_options)?;
+            }
+        }
+        Some(options::Commands::Use { version, track, install, interactive }) => {
+            if interactive {
+                if let Some(version) = commands::r#use::pick_version()? {
+                    commands::r#use::execute(Some(&version), false, install)?;
+                }
+            } else {
+                commands::r#use::execute(version.map(alias).as_deref(), track, install)?;
+            }
         }
-        Some(options::Commands::Use { version }) => {
-            commands::r#use::execute(&version)?;
+        Some(options::Commands::List { filter, remote, security, lts, channel, all, limit, size, json, offline }) => {
+            let list_options = commands::list::ListOptions {
+                remote,
+                security,
+                lts,
+                filter,
+                channel,
+                all,
+                limit,
+                size,
+                json: json_output(json),
+                offline,
+            };
+            commands::list::execute(&list_options)?;
         }
-        Some(options::Commands::List { remote }) => {
-            commands::list::execute(remote)?;
+        Some(options::Commands::Remove { versions, force }) => {
+            let versions: Vec<String> = versions.into_iter().map(alias).collect();
+            commands::remove::execute(&versions, force)?;
         }
-        Some(options::Commands::Remove { version }) => {
-            commands::remove::execute(&version)?;
+        Some(options::Commands::Reinstall { version }) => {
+            commands::reinstall::execute(&alias(version))?;
         }
         Some(options::Commands::GlobalList) => {
             commands::global_list::execute()?;
@@ -43,6 +147,127 @@ fn main() -> anyhow::Result<()> {
         Some(options::Commands::Update) => {
             commands::update::execute()?;
         }
+        Some(options::Commands::Status) => {
+            commands::status::execute()?;
+        }
+        Some(options::Commands::Exec { version, env_files, command }) => {
+            commands::exec::execute(&alias(version), &env_files, &command)?;
+        }
+        Some(options::Commands::VersionEnv { version, vars, unset }) => {
+            commands::version_env::execute(&alias(version), &vars, &unset)?;
+        }
+        Some(options::Commands::Run { env_files, script, args }) => {
+            commands::run::execute(&script, &env_files, &args)?;
+        }
+        Some(options::Commands::Dlx { node, command }) => {
+            commands::dlx::execute(node.map(alias).as_deref(), &command)?;
+        }
+        Some(options::Commands::Projects { command: options::ProjectsCommands::Scan { dir } }) => {
+            commands::projects::scan(dir.as_deref())?;
+        }
+        Some(options::Commands::UpgradeNode { lts }) => {
+            commands::upgrade_node::execute(lts)?;
+        }
+        Some(options::Commands::Upgrade { target, migrate_globals, prune }) => {
+            commands::upgrade::execute(target.as_deref(), migrate_globals, prune)?;
+        }
+        Some(options::Commands::AutoUpdate { command }) => match command {
+            options::AutoUpdateCommands::Enable { cadence } => commands::auto_update::enable(cadence)?,
+            options::AutoUpdateCommands::Disable => commands::auto_update::disable()?,
+            options::AutoUpdateCommands::Status => commands::auto_update::status()?,
+        },
+        Some(options::Commands::Headers { command: options::HeadersCommands::Install { version } }) => {
+            commands::headers::execute(&alias(version))?;
+        }
+        Some(options::Commands::Gc { dry_run }) => {
+            commands::gc::execute(dry_run)?;
+        }
+        Some(options::Commands::Cache { command }) => match command {
+            options::CacheCommands::List => commands::cache::list()?,
+            options::CacheCommands::Size => commands::cache::size()?,
+            options::CacheCommands::Clear => commands::cache::clear()?,
+        },
+        Some(options::Commands::Prune { roots, policy, dry_run }) => {
+            commands::prune::execute(&roots, policy, dry_run)?;
+        }
+        Some(options::Commands::Url { version, target_os, target_arch }) => {
+            commands::url::execute(&alias(version), target_os.as_deref(), target_arch.as_deref())?;
+        }
+        Some(options::Commands::Resolve { dir, no_daemon }) => {
+            commands::resolve::execute(dir.as_deref(), no_daemon)?;
+        }
+        Some(options::Commands::Daemon { command }) => match command {
+            options::DaemonCommands::Run => commands::daemon::run()?,
+            options::DaemonCommands::Stop => commands::daemon::stop()?,
+            options::DaemonCommands::Status => commands::daemon::status()?,
+        },
+        Some(options::Commands::Pin { version, mise }) => {
+            commands::pin::execute(&alias(version), mise)?;
+        }
+        Some(options::Commands::Env { version, shell, no_install }) => {
+            commands::env::execute(version.map(alias).as_deref(), shell, no_install)?;
+        }
+        Some(options::Commands::Init { shell }) => {
+            commands::init::execute(shell)?;
+        }
+        Some(options::Commands::Completions { shell }) => {
+            commands::completions::execute(shell)?;
+        }
+        Some(options::Commands::Setup { shell, print_only }) => {
+            commands::setup::execute(shell, print_only)?;
+        }
+        Some(options::Commands::Which { version }) => {
+            commands::which::execute(version.map(alias).as_deref())?;
+        }
+        Some(options::Commands::Alias { name, version }) => {
+            commands::alias::execute(name.as_deref(), version.as_deref())?;
+        }
+        Some(options::Commands::Unalias { name }) => {
+            commands::alias::unalias(&name)?;
+        }
+        Some(options::Commands::CompletionHelper { words }) => {
+            commands::completions::helper(&words)?;
+        }
+        Some(options::Commands::BugReport { json }) => {
+            commands::bug_report::execute(json_output(json))?;
+        }
+        Some(options::Commands::Telemetry { command }) => match command {
+            options::TelemetryCommands::On { local_only, endpoint } => commands::telemetry::enable(local_only, endpoint)?,
+            options::TelemetryCommands::Off => commands::telemetry::disable()?,
+            options::TelemetryCommands::Status => commands::telemetry::status()?,
+        },
+        Some(options::Commands::Doctor { json, fix, command }) => match command {
+            Some(options::DoctorCommands::Network) => commands::doctor::network(json_output(json))?,
+            None => commands::doctor::execute(json_output(json), fix)?,
+        },
+        Some(options::Commands::Verify { version, deep }) => {
+            commands::verify::execute(version.map(alias).as_deref(), deep)?;
+        }
+        Some(options::Commands::Outdated { json }) => {
+            commands::outdated::execute(json_output(json))?;
+        }
+        Some(options::Commands::Info { version, json }) => {
+            commands::info::execute(&alias(version), json_output(json))?;
+        }
+        Some(options::Commands::AuditVersions { versions, json }) => {
+            let versions: Vec<String> = versions.into_iter().map(alias).collect();
+            commands::audit_versions::execute(&versions, json_output(json))?;
+        }
+        Some(options::Commands::Recent { limit }) => {
+            commands::recent::execute(limit)?;
+        }
+        Some(options::Commands::Ensure { version, project, changed_exit_code }) => {
+            commands::ensure::execute(version.map(alias).as_deref(), project, changed_exit_code)?;
+        }
+        Some(options::Commands::Generate { editor }) => {
+            commands::generate::execute(editor)?;
+        }
+        Some(options::Commands::Link { dir, name }) => {
+            commands::link::execute(&dir, &name)?;
+        }
+        Some(options::Commands::Unlink { version }) => {
+            commands::link::unlink(&version)?;
+        }
         None => {
             let mut cmd = options::Cli::command();
             cmd.print_help()?;
@@ -70,7 +295,7 @@ pub fn create_alias() -> anyhow::Result<()> {
         writeln!(file, "@echo off")?;
         writeln!(file, "\"{}\" %*", executable.display())?;
         
-        println!("Created alias: {} -> {}", "nsk".green(), "node-spark".bright_green());
+        say!("Created alias: {} {} {}", "nsk".success(), options::symbols::arrow(), "node-spark".brand());
     }
     
     #[cfg(not(target_os = "windows"))]
@@ -90,21 +315,23 @@ pub fn create_alias() -> anyhow::Result<()> {
             .args(["-s", &executable.to_string_lossy(), &nsk_path.to_string_lossy()])
             .output()?;
             
-        println!("Created alias: {} -> {}", "nsk".green(), "node-spark".bright_green());
+        say!("Created alias: {} {} {}", "nsk".success(), options::symbols::arrow(), "node-spark".brand());
     }
     
     Ok(())
 }
 
-fn check_and_create_alias() -> anyhow::Result<()> {
+pub fn alias_path() -> anyhow::Result<std::path::PathBuf> {
     let executable = std::env::current_exe()?;
-    let nsk_path = executable.parent().unwrap().join(if cfg!(target_os = "windows") {
+    Ok(executable.parent().unwrap().join(if cfg!(target_os = "windows") {
         "nsk.bat"
     } else {
         "nsk"
-    });
+    }))
+}
 
-    if !nsk_path.exists() {
+fn check_and_create_alias() -> anyhow::Result<()> {
+    if !alias_path()?.exists() {
         create_alias()?;
     }
 
@@ -0,0 +1,42 @@
+use clap::ValueEnum;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+static JSON_EVENTS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_format(format: LogFormat) {
+    JSON_EVENTS.store(format == LogFormat::Json, Ordering::SeqCst);
+}
+
+pub fn is_enabled() -> bool {
+    JSON_EVENTS.load(Ordering::SeqCst)
+}
+
+pub fn emit(event: &str, fields: Value) {
+    if !is_enabled() {
+        return;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+
+    let mut record = json!({ "event": event, "timestamp_ms": timestamp });
+    if let (Some(record), Value::Object(fields)) = (record.as_object_mut(), fields) {
+        record.extend(fields);
+    }
+
+    eprintln!("{}", record);
+}
+
+pub fn elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis() as u64
+}
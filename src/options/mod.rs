@@ -1,7 +1,20 @@
+pub mod events;
+pub mod output;
+pub mod picker;
+pub mod prompt;
+pub mod symbols;
+pub mod theme;
+pub mod tls;
 pub mod verbose;
 pub mod version;
 
 use clap::{Parser, Subcommand, ArgAction};
+use std::path::PathBuf;
+use crate::commands::auto_update::Cadence;
+use crate::commands::env::Shell;
+use crate::utils::channel::Channel;
+use crate::utils::platform::Flavor;
+use crate::commands::generate::Editor;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -15,30 +28,436 @@ pub struct Cli {
 
     #[arg(short, long, action = ArgAction::SetTrue)]
     pub verbose: bool,
+
+    #[arg(short = 'y', long, global = true, action = ArgAction::SetTrue)]
+    pub yes: bool,
+
+    #[arg(long, value_enum, global = true, default_value_t = events::LogFormat::Text)]
+    pub log_format: events::LogFormat,
+
+    #[arg(long, global = true, action = ArgAction::SetTrue)]
+    pub ascii: bool,
+
+    #[arg(short, long, value_enum, global = true, default_value_t = output::OutputFormat::Table)]
+    pub output: output::OutputFormat,
+
+    #[arg(long, global = true, value_name = "PATH")]
+    pub ca_bundle: Option<PathBuf>,
+
+    #[arg(long, global = true, action = ArgAction::SetTrue)]
+    pub insecure: bool,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     Install {
-        version: String,
+        versions: Vec<String>,
+
+        #[arg(long)]
+        from_dir: Option<PathBuf>,
+
+        #[arg(long)]
+        with_headers: bool,
+
+        #[arg(long)]
+        no_verify: bool,
+
+        #[arg(long, conflicts_with = "no_verify")]
+        verify_signatures: bool,
+
+        #[arg(long, conflicts_with_all = ["with_headers", "verify_signatures"])]
+        offline: bool,
+
+        #[arg(long)]
+        force: bool,
+
+        #[arg(long, value_enum)]
+        flavor: Option<Flavor>,
+
+        #[arg(long)]
+        arch: Option<String>,
+
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["from_dir", "offline"])]
+        file: Option<PathBuf>,
+
+        #[arg(short, long, conflicts_with_all = ["versions", "from_dir", "offline", "file"])]
+        interactive: bool,
     },
 
     #[command(name = "use")]
     Use {
-        version: String,
+        version: Option<String>,
+
+        #[arg(long)]
+        track: bool,
+
+        #[arg(long)]
+        install: bool,
+
+        #[arg(short, long, conflicts_with_all = ["version", "track"])]
+        interactive: bool,
     },
 
     Remove {
+        versions: Vec<String>,
+
+        #[arg(long)]
+        force: bool,
+    },
+
+    Reinstall {
         version: String,
     },
 
     List {
+        #[arg(requires = "remote")]
+        filter: Option<String>,
+
         #[arg(short, long)]
         remote: bool,
+
+        #[arg(long, requires = "remote")]
+        security: bool,
+
+        #[arg(long, requires = "remote")]
+        lts: bool,
+
+        #[arg(long, value_enum, requires = "remote", default_value_t = Channel::Release)]
+        channel: Channel,
+
+        #[arg(short, long, requires = "remote")]
+        all: bool,
+
+        #[arg(long, requires = "remote", conflicts_with = "all")]
+        limit: Option<usize>,
+
+        #[arg(long, conflicts_with = "remote")]
+        size: bool,
+
+        #[arg(long)]
+        json: bool,
+
+        #[arg(long, requires = "remote")]
+        offline: bool,
     },
 
     #[command(name = "global-list")]
     GlobalList,
 
     Update,
+
+    Status,
+
+    Exec {
+        version: String,
+
+        #[arg(long = "env-file")]
+        env_files: Vec<PathBuf>,
+
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
+
+    #[command(name = "version-env")]
+    VersionEnv {
+        version: String,
+
+        vars: Vec<String>,
+
+        #[arg(long)]
+        unset: Vec<String>,
+    },
+
+    Run {
+        #[arg(long = "env-file")]
+        env_files: Vec<PathBuf>,
+
+        script: String,
+
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    Dlx {
+        #[arg(long)]
+        node: Option<String>,
+
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true, required = true)]
+        command: Vec<String>,
+    },
+
+    Projects {
+        #[command(subcommand)]
+        command: ProjectsCommands,
+    },
+
+    #[command(name = "upgrade-node")]
+    UpgradeNode {
+        #[arg(long)]
+        lts: bool,
+    },
+
+    Upgrade {
+        target: Option<String>,
+
+        #[arg(long)]
+        migrate_globals: bool,
+
+        #[arg(long)]
+        prune: bool,
+    },
+
+    #[command(name = "auto-update")]
+    AutoUpdate {
+        #[command(subcommand)]
+        command: AutoUpdateCommands,
+    },
+
+    Doctor {
+        #[arg(long, global = true)]
+        json: bool,
+
+        #[arg(long)]
+        fix: bool,
+
+        #[command(subcommand)]
+        command: Option<DoctorCommands>,
+    },
+
+    Verify {
+        version: Option<String>,
+
+        #[arg(long)]
+        deep: bool,
+    },
+
+    Outdated {
+        #[arg(long)]
+        json: bool,
+    },
+
+    Info {
+        version: String,
+
+        #[arg(long)]
+        json: bool,
+    },
+
+    #[command(name = "audit-versions")]
+    AuditVersions {
+        versions: Vec<String>,
+
+        #[arg(long)]
+        json: bool,
+    },
+
+    Recent {
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+    },
+
+    Ensure {
+        #[arg(required_unless_present = "project")]
+        version: Option<String>,
+
+        #[arg(long, conflicts_with = "version")]
+        project: bool,
+
+        #[arg(long)]
+        changed_exit_code: Option<i32>,
+    },
+
+    Generate {
+        #[arg(value_enum)]
+        editor: Editor,
+    },
+
+    Link {
+        dir: PathBuf,
+
+        #[arg(long = "as")]
+        name: String,
+    },
+
+    Unlink {
+        version: String,
+    },
+
+    Telemetry {
+        #[command(subcommand)]
+        command: TelemetryCommands,
+    },
+
+    Headers {
+        #[command(subcommand)]
+        command: HeadersCommands,
+    },
+
+    Gc {
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+
+    Prune {
+        #[arg(long = "root")]
+        roots: Vec<PathBuf>,
+
+        #[arg(long)]
+        policy: bool,
+
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    Url {
+        version: String,
+
+        #[arg(long)]
+        target_os: Option<String>,
+
+        #[arg(long)]
+        target_arch: Option<String>,
+    },
+
+    Resolve {
+        dir: Option<PathBuf>,
+
+        #[arg(long)]
+        no_daemon: bool,
+    },
+
+    Daemon {
+        #[command(subcommand)]
+        command: DaemonCommands,
+    },
+
+    Pin {
+        version: String,
+
+        #[arg(long)]
+        mise: bool,
+    },
+
+    Env {
+        version: Option<String>,
+
+        #[arg(long, value_enum)]
+        shell: Option<Shell>,
+
+        #[arg(long, action = ArgAction::SetTrue)]
+        no_install: bool,
+    },
+
+    Init {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    Setup {
+        #[arg(long, value_enum)]
+        shell: Option<Shell>,
+
+        #[arg(long)]
+        print_only: bool,
+    },
+
+    Which {
+        version: Option<String>,
+    },
+
+    Alias {
+        name: Option<String>,
+
+        version: Option<String>,
+    },
+
+    Unalias {
+        name: String,
+    },
+
+    #[command(name = "completion-helper", hide = true)]
+    CompletionHelper {
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        words: Vec<String>,
+    },
+
+    #[command(name = "bug-report")]
+    BugReport {
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProjectsCommands {
+    Scan {
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AutoUpdateCommands {
+    Enable {
+        #[arg(long, value_enum, default_value_t = Cadence::Weekly)]
+        cadence: Cadence,
+    },
+
+    Disable,
+
+    Status,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HeadersCommands {
+    Install {
+        version: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DaemonCommands {
+    Run,
+
+    Stop,
+
+    Status,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheCommands {
+    List,
+
+    Size,
+
+    Clear,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TelemetryCommands {
+    On {
+        #[arg(long)]
+        local_only: bool,
+
+        #[arg(long)]
+        endpoint: Option<String>,
+    },
+
+    Off,
+
+    Status,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DoctorCommands {
+    Network,
 }
\ No newline at end of file
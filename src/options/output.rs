@@ -0,0 +1,94 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use indicatif::MultiProgress;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Table,
+    Plain,
+    Json,
+}
+
+static FORMAT: AtomicU8 = AtomicU8::new(OutputFormat::Table as u8);
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+static ACTIVE_PROGRESS: Mutex<Option<MultiProgress>> = Mutex::new(None);
+
+pub fn set_format(format: OutputFormat) {
+    FORMAT.store(format as u8, Ordering::SeqCst);
+    if format != OutputFormat::Table {
+        colored::control::set_override(false);
+    }
+}
+
+pub fn format() -> OutputFormat {
+    match FORMAT.load(Ordering::SeqCst) {
+        x if x == OutputFormat::Plain as u8 => OutputFormat::Plain,
+        x if x == OutputFormat::Json as u8 => OutputFormat::Json,
+        _ => OutputFormat::Table,
+    }
+}
+
+pub fn is_json() -> bool {
+    format() == OutputFormat::Json
+}
+
+// For commands whose stdout is evaluated by a shell, such as `env`
+pub fn reserve_stdout() {
+    STDOUT_RESERVED.store(true, Ordering::SeqCst);
+}
+
+pub fn human_to_stderr() -> bool {
+    is_json() || STDOUT_RESERVED.load(Ordering::SeqCst)
+}
+
+// While progress bars are drawn from several threads, lines must be printed around them
+pub fn set_progress(progress: Option<MultiProgress>) {
+    *ACTIVE_PROGRESS.lock().unwrap() = progress;
+}
+
+pub fn write_line(line: &str) {
+    let print = || {
+        if human_to_stderr() {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
+    };
+    let progress = ACTIVE_PROGRESS.lock().unwrap().clone();
+    match progress {
+        Some(progress) => progress.suspend(print),
+        None => print(),
+    }
+}
+
+// Same routing as write_line, without the newline, for questions answered on the same line
+pub fn write_prompt(text: &str) -> io::Result<()> {
+    if human_to_stderr() {
+        eprint!("{}", text);
+        io::stderr().flush()
+    } else {
+        print!("{}", text);
+        io::stdout().flush()
+    }
+}
+
+pub fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Prints a human-readable line. With `--output json`, or when stdout is
+/// reserved for shell code, it goes to stderr so stdout only carries data.
+#[macro_export]
+macro_rules! say {
+    () => {
+        $crate::say!("")
+    };
+    ($($arg:tt)*) => {
+        $crate::options::output::write_line(&format!($($arg)*))
+    };
+}
@@ -0,0 +1,139 @@
+use anyhow::{Result, anyhow};
+use console::{Key, Term};
+use crate::options::symbols;
+use crate::options::theme::Themed;
+
+const VISIBLE_ROWS: usize = 15;
+
+pub struct PickerItem {
+    pub label: String,
+    pub note: Option<String>,
+    pub locked: bool,
+}
+
+pub fn multi_select(title: &str, items: &[PickerItem]) -> Result<Option<Vec<usize>>> {
+    let mut checked = vec![false; items.len()];
+    let picked = run(title, "space to select, enter to confirm, esc to cancel", items, Some(&mut checked))?;
+    Ok(picked.map(|_| (0..items.len()).filter(|&i| checked[i]).collect()))
+}
+
+pub fn select(title: &str, items: &[PickerItem]) -> Result<Option<usize>> {
+    let term = Term::stdout();
+    if !term.is_term() {
+        return Err(anyhow!("{} needs an interactive terminal", title));
+    }
+    if items.is_empty() {
+        return Err(anyhow!("Nothing to choose from"));
+    }
+
+    let mut query = String::new();
+    let mut cursor = 0;
+    term.hide_cursor()?;
+    let result = loop {
+        let matches: Vec<usize> = (0..items.len()).filter(|&i| fuzzy_match(&query, &items[i])).collect();
+        cursor = cursor.min(matches.len().saturating_sub(1));
+        let offset = cursor.saturating_sub(VISIBLE_ROWS - 1);
+        let visible: Vec<usize> = matches.iter().copied().skip(offset).take(VISIBLE_ROWS).collect();
+
+        term.write_line(&format!("{} {}", title, "(type to filter, enter to choose, esc to cancel)".muted()))?;
+        term.write_line(&format!("{} {}", symbols::pointer().info(), query))?;
+        for (row, &i) in visible.iter().enumerate() {
+            term.write_line(&render(&items[i], offset + row == cursor, None))?;
+        }
+        if matches.is_empty() {
+            term.write_line(&"  no matches".muted().to_string())?;
+        }
+
+        let key = term.read_key()?;
+        term.clear_last_lines(visible.len().max(1) + 2)?;
+        match key {
+            Key::ArrowUp => cursor = cursor.saturating_sub(1),
+            Key::ArrowDown if cursor + 1 < matches.len() => cursor += 1,
+            Key::Backspace => {
+                query.pop();
+                cursor = 0;
+            }
+            Key::Char(c) if !c.is_control() => {
+                query.push(c);
+                cursor = 0;
+            }
+            Key::Enter if !matches.is_empty() => break Some(matches[cursor]),
+            Key::Escape | Key::CtrlC => break None,
+            _ => {}
+        }
+    };
+    term.show_cursor()?;
+
+    Ok(result)
+}
+
+fn fuzzy_match(query: &str, item: &PickerItem) -> bool {
+    let haystack = format!("{} {}", item.label, item.note.as_deref().unwrap_or_default()).to_lowercase();
+    let mut chars = haystack.chars();
+    query.to_lowercase().chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|needle| chars.any(|c| c == needle))
+}
+
+fn run(title: &str, hint: &str, items: &[PickerItem], mut checked: Option<&mut Vec<bool>>) -> Result<Option<usize>> {
+    let term = Term::stdout();
+    if !term.is_term() {
+        return Err(anyhow!("{} needs an interactive terminal", title));
+    }
+    if items.iter().all(|item| item.locked) {
+        return Err(anyhow!("Nothing to choose from"));
+    }
+
+    let mut cursor = items.iter().position(|item| !item.locked).unwrap_or(0);
+    term.hide_cursor()?;
+    let result = loop {
+        term.write_line(&format!("{} {}", title, format!("({})", hint).muted()))?;
+        for (i, item) in items.iter().enumerate() {
+            term.write_line(&render(item, i == cursor, checked.as_ref().map(|checked| checked[i])))?;
+        }
+
+        let key = term.read_key()?;
+        term.clear_last_lines(items.len() + 1)?;
+        match key {
+            Key::ArrowUp | Key::Char('k') => cursor = step(items, cursor, false),
+            Key::ArrowDown | Key::Char('j') => cursor = step(items, cursor, true),
+            Key::Char(' ') => {
+                if let Some(ref mut checked) = checked {
+                    if !items[cursor].locked {
+                        checked[cursor] = !checked[cursor];
+                    }
+                }
+            }
+            Key::Enter => break Some(cursor),
+            Key::Escape | Key::Char('q') | Key::CtrlC => break None,
+            _ => {}
+        }
+    };
+    term.show_cursor()?;
+
+    Ok(result)
+}
+
+fn render(item: &PickerItem, focused: bool, checked: Option<bool>) -> String {
+    let pointer = if focused { symbols::pointer().info().to_string() } else { " ".repeat(symbols::pointer().chars().count()) };
+    let checkbox = match checked {
+        Some(checked) => format!("{} ", symbols::checkbox(checked)),
+        None => String::new(),
+    };
+    let label = if item.locked { item.label.muted().to_string() } else { item.label.clone() };
+    let note = item.note.as_ref().map(|note| format!(" {}", note.muted())).unwrap_or_default();
+
+    format!("{} {}{}{}", pointer, checkbox, label, note)
+}
+
+fn step(items: &[PickerItem], cursor: usize, forward: bool) -> usize {
+    let len = items.len();
+    let mut next = cursor;
+    for _ in 0..len {
+        next = if forward { (next + 1) % len } else { (next + len - 1) % len };
+        if !items[next].locked {
+            return next;
+        }
+    }
+    cursor
+}
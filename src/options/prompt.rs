@@ -0,0 +1,31 @@
+use anyhow::{Result, anyhow};
+use std::io::{self, IsTerminal};
+use crate::options::output;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+pub fn set_assume_yes(yes: bool) {
+    ASSUME_YES.store(yes, Ordering::SeqCst);
+}
+
+pub fn assume_yes() -> bool {
+    ASSUME_YES.load(Ordering::SeqCst)
+}
+
+pub fn confirm(question: &str) -> Result<bool> {
+    if assume_yes() {
+        return Ok(true);
+    }
+
+    if !io::stdin().is_terminal() {
+        return Err(anyhow!("{} (re-run with --yes to confirm non-interactively)", question));
+    }
+
+    output::write_prompt(&format!("{} [y/N] ", question))?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
@@ -0,0 +1,87 @@
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+pub fn init(ascii_flag: bool, configured: Option<bool>) {
+    let ascii = ascii_flag || configured.unwrap_or_else(|| !terminal_supports_unicode());
+    ASCII.store(ascii, Ordering::SeqCst);
+}
+
+pub fn is_ascii() -> bool {
+    ASCII.load(Ordering::SeqCst)
+}
+
+fn terminal_supports_unicode() -> bool {
+    if env::var("TERM").map(|term| term == "dumb").unwrap_or(false) {
+        return false;
+    }
+
+    if cfg!(target_os = "windows") {
+        return env::var_os("WT_SESSION").is_some() || env::var_os("TERM_PROGRAM").is_some();
+    }
+
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    locale.contains("utf-8") || locale.contains("utf8")
+}
+
+fn pick(unicode: &'static str, ascii: &'static str) -> &'static str {
+    if is_ascii() { ascii } else { unicode }
+}
+
+pub fn arrow() -> &'static str {
+    pick("→", "->")
+}
+
+pub fn back_arrow() -> &'static str {
+    pick("←", "<-")
+}
+
+pub fn ok() -> &'static str {
+    pick("✓", "[ok]")
+}
+
+pub fn fail() -> &'static str {
+    pick("✗", "[fail]")
+}
+
+pub fn skip() -> &'static str {
+    pick("–", "[skip]")
+}
+
+pub fn shield() -> &'static str {
+    pick("🛡", "[sec]")
+}
+
+pub fn marker_indent() -> String {
+    " ".repeat(fail().chars().count() + 1)
+}
+
+pub fn progress_chars() -> &'static str {
+    pick("█▉▊▋▌▍▎▏ ", "#>-")
+}
+
+pub fn spinner_ticks() -> &'static [&'static str] {
+    if is_ascii() {
+        &["-", "\\", "|", "/", "-"]
+    } else {
+        &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏", "✓"]
+    }
+}
+
+pub fn pointer() -> &'static str {
+    pick("❯", ">")
+}
+
+pub fn checkbox(checked: bool) -> &'static str {
+    match checked {
+        true => pick("◉", "[x]"),
+        false => pick("◯", "[ ]"),
+    }
+}
@@ -0,0 +1,153 @@
+use colored::{Color, ColoredString, Colorize};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use crate::config::{self, Config};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Current,
+    Installed,
+    Success,
+    Warning,
+    Error,
+    Info,
+    Emphasis,
+    Brand,
+    Muted,
+}
+
+impl Role {
+    const ALL: [Role; 9] = [
+        Role::Current, Role::Installed, Role::Success, Role::Warning, Role::Error,
+        Role::Info, Role::Emphasis, Role::Brand, Role::Muted,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Role::Current => "current",
+            Role::Installed => "installed",
+            Role::Success => "success",
+            Role::Warning => "warning",
+            Role::Error => "error",
+            Role::Info => "info",
+            Role::Emphasis => "emphasis",
+            Role::Brand => "brand",
+            Role::Muted => "muted",
+        }
+    }
+}
+
+struct Theme {
+    colors: BTreeMap<&'static str, Color>,
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+fn preset(name: &str) -> Vec<(Role, Color)> {
+    match name {
+        "light" => vec![
+            (Role::Current, Color::Blue),
+            (Role::Installed, Color::Magenta),
+            (Role::Success, Color::Blue),
+            (Role::Warning, Color::Magenta),
+            (Role::Error, Color::Red),
+            (Role::Info, Color::Cyan),
+            (Role::Emphasis, Color::Black),
+            (Role::Brand, Color::Blue),
+        ],
+        _ => vec![
+            (Role::Current, Color::Green),
+            (Role::Installed, Color::Yellow),
+            (Role::Success, Color::Green),
+            (Role::Warning, Color::Yellow),
+            (Role::Error, Color::Red),
+            (Role::Info, Color::Cyan),
+            (Role::Emphasis, Color::BrightWhite),
+            (Role::Brand, Color::BrightGreen),
+        ],
+    }
+}
+
+// Built on first use, so --version and --help never read the config file
+fn theme() -> &'static Theme {
+    THEME.get_or_init(|| load(config::load_config().ok().as_ref()))
+}
+
+fn load(config: Option<&Config>) -> Theme {
+    let preset_name = config.and_then(|c| c.theme.as_deref()).unwrap_or("dark");
+
+    let mut colors: BTreeMap<&'static str, Color> = preset(preset_name)
+        .into_iter()
+        .map(|(role, color)| (role.name(), color))
+        .collect();
+
+    if let Some(config) = config {
+        for role in Role::ALL {
+            if let Some(color) = config.colors.get(role.name()) {
+                if let Ok(color) = color.parse::<Color>() {
+                    colors.insert(role.name(), color);
+                }
+            }
+        }
+    }
+
+    Theme { colors }
+}
+
+// The role's color as an indicatif template style such as "green" or "green.bright"
+pub fn progress_style(role: Role) -> String {
+    let Some(color) = theme().colors.get(role.name()) else {
+        return "dim".to_string();
+    };
+    let (name, bright) = match color {
+        Color::Black => ("black", false),
+        Color::Red => ("red", false),
+        Color::Green => ("green", false),
+        Color::Yellow => ("yellow", false),
+        Color::Blue => ("blue", false),
+        Color::Magenta => ("magenta", false),
+        Color::Cyan => ("cyan", false),
+        Color::White => ("white", false),
+        Color::BrightBlack => ("black", true),
+        Color::BrightRed => ("red", true),
+        Color::BrightGreen => ("green", true),
+        Color::BrightYellow => ("yellow", true),
+        Color::BrightBlue => ("blue", true),
+        Color::BrightMagenta => ("magenta", true),
+        Color::BrightCyan => ("cyan", true),
+        Color::BrightWhite => ("white", true),
+        Color::TrueColor { r, g, b } => {
+            // Nearest entry of the 256-color cube
+            let level = |c: &u8| *c as u16 * 5 / 255;
+            return (16 + 36 * level(r) + 6 * level(g) + level(b)).to_string();
+        }
+    };
+    if bright { format!("{}.bright", name) } else { name.to_string() }
+}
+
+pub trait Themed {
+    fn styled(&self, role: Role) -> ColoredString;
+
+    fn current(&self) -> ColoredString { self.styled(Role::Current) }
+    fn installed(&self) -> ColoredString { self.styled(Role::Installed) }
+    fn success(&self) -> ColoredString { self.styled(Role::Success) }
+    fn warning(&self) -> ColoredString { self.styled(Role::Warning) }
+    fn error(&self) -> ColoredString { self.styled(Role::Error) }
+    fn info(&self) -> ColoredString { self.styled(Role::Info) }
+    fn emphasis(&self) -> ColoredString { self.styled(Role::Emphasis) }
+    fn brand(&self) -> ColoredString { self.styled(Role::Brand) }
+    fn muted(&self) -> ColoredString { self.styled(Role::Muted) }
+}
+
+impl<T: AsRef<str> + ?Sized> Themed for T {
+    fn styled(&self, role: Role) -> ColoredString {
+        let text = self.as_ref();
+        let color = theme().colors.get(role.name()).copied();
+
+        match (color, role) {
+            (Some(color), _) => text.color(color),
+            (None, Role::Muted) => text.dimmed(),
+            (None, _) => text.normal(),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static CA_BUNDLE: Mutex<Option<PathBuf>> = Mutex::new(None);
+static INSECURE: AtomicBool = AtomicBool::new(false);
+
+pub fn init(ca_bundle: Option<PathBuf>, insecure: bool) {
+    *CA_BUNDLE.lock().unwrap() = ca_bundle;
+    INSECURE.store(insecure, Ordering::SeqCst);
+}
+
+pub fn ca_bundle() -> Option<PathBuf> {
+    CA_BUNDLE.lock().unwrap().clone()
+}
+
+pub fn is_insecure() -> bool {
+    INSECURE.load(Ordering::SeqCst)
+}
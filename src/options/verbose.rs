@@ -1,5 +1,6 @@
-use colored::Colorize;
 use std::sync::atomic::{AtomicBool, Ordering};
+use crate::options::output;
+use crate::options::theme::Themed;
 
 static VERBOSE: AtomicBool = AtomicBool::new(false);
 
@@ -13,6 +14,6 @@ pub fn is_verbose() -> bool {
 
 pub fn log(message: &str) {
     if is_verbose() {
-        println!("{} {}", "[VERBOSE]".blue(), message);
+        output::write_line(&format!("{} {}", "[VERBOSE]".info(), message));
     }
 }
\ No newline at end of file
@@ -1,9 +1,9 @@
-use colored::Colorize;
+use crate::options::theme::Themed;
 
 pub fn show() {
     let version = env!("CARGO_PKG_VERSION");
     let name = env!("CARGO_PKG_NAME");
     
-    println!("{} v{}", name.bright_green(), version.bright_white());
-    println!("Author: {}", env!("CARGO_PKG_AUTHORS").bright_blue());
+    println!("{} v{}", name.brand(), version.emphasis());
+    println!("Author: {}", env!("CARGO_PKG_AUTHORS").info());
 }
\ No newline at end of file
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use crate::config::NodeSparkDirs;
+use crate::options::verbose;
+use crate::utils::{self, disk};
+use crate::utils::platform::Platform;
+
+pub fn lookup(dirs: &NodeSparkDirs, version: &str, platform: &Platform) -> Option<PathBuf> {
+    platform.artifact_names(version).into_iter()
+        .map(|name| dirs.cache_dir.join(name))
+        .find(|path| path.is_file())
+}
+
+pub fn store(dirs: &NodeSparkDirs, archive: &Path) -> Result<()> {
+    let name = archive.file_name()
+        .ok_or_else(|| anyhow::anyhow!("{} has no file name", archive.display()))?;
+    let path = dirs.cache_dir.join(name);
+    verbose::log(&format!("Caching {} at {}", archive.display(), path.display()));
+
+    if fs::rename(archive, &path).is_err() {
+        fs::copy(archive, &path)?;
+    }
+    Ok(())
+}
+
+pub fn cached_versions(dirs: &NodeSparkDirs) -> Result<Vec<String>> {
+    let entries = match fs::read_dir(&dirs.cache_dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let suffixes: Vec<String> = Platform::host().artifact_names("").into_iter()
+        .map(|name| name.trim_start_matches("node-v").to_string())
+        .collect();
+
+    let mut versions = Vec::new();
+    for entry in entries {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        let version = name.strip_prefix("node-v")
+            .and_then(|rest| suffixes.iter().find_map(|suffix| rest.strip_suffix(suffix.as_str())));
+        if let Some(version) = version {
+            versions.push(version.to_string());
+        }
+    }
+
+    utils::sort_versions_desc(&mut versions);
+    versions.dedup();
+    Ok(versions)
+}
+
+pub fn touch(path: &Path) {
+    if let Ok(file) = File::options().write(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+}
+
+pub fn total_size(dirs: &NodeSparkDirs) -> u64 {
+    disk::dir_size(&dirs.cache_dir)
+}
+
+// cache_max_mb in bytes; a huge setting means "no limit" rather than overflowing
+pub fn max_bytes(max_mb: u64) -> u64 {
+    max_mb.saturating_mul(1024 * 1024)
+}
+
+pub fn evict(dirs: &NodeSparkDirs, max_bytes: u64) -> Result<Vec<PathBuf>> {
+    let mut archives = Vec::new();
+    for entry in fs::read_dir(&dirs.cache_dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            archives.push((metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH), metadata.len(), entry.path()));
+        }
+    }
+    archives.sort();
+
+    let mut total: u64 = archives.iter().map(|(_, size, _)| size).sum();
+    let mut evicted = Vec::new();
+    for (_, size, path) in archives {
+        if total <= max_bytes {
+            break;
+        }
+        if let Err(e) = fs::remove_file(&path) {
+            if e.kind() != io::ErrorKind::NotFound {
+                return Err(e.into());
+            }
+        }
+        total -= size;
+        evicted.push(path);
+    }
+
+    Ok(evicted)
+}
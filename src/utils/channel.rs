@@ -0,0 +1,56 @@
+use clap::ValueEnum;
+use crate::utils;
+
+const DEFAULT_DOWNLOAD_ROOT: &str = "https://nodejs.org/download";
+
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    #[default]
+    Release,
+    Nightly,
+    Rc,
+}
+
+impl Channel {
+    pub fn of(version: &str) -> Channel {
+        if version.contains("-nightly") {
+            Channel::Nightly
+        } else if version.contains("-rc") {
+            Channel::Rc
+        } else {
+            Channel::Release
+        }
+    }
+
+    pub fn for_spec(spec: &str) -> Channel {
+        let spec = spec.to_lowercase();
+        if spec == "nightly" || spec.starts_with("nightly-") {
+            Channel::Nightly
+        } else if spec == "rc" {
+            Channel::Rc
+        } else {
+            Channel::of(&spec)
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Channel::Release => "release",
+            Channel::Nightly => "nightly",
+            Channel::Rc => "rc",
+        }
+    }
+
+    pub fn base_url(&self) -> String {
+        let dist = utils::dist_url();
+        if *self == Channel::Release {
+            return dist;
+        }
+
+        // Mirrors of the official layout keep the other channels next to dist/
+        let root = dist.strip_suffix("/dist")
+            .map(|root| format!("{}/download", root))
+            .unwrap_or_else(|| DEFAULT_DOWNLOAD_ROOT.to_string());
+        format!("{}/{}", root, self.name())
+    }
+}
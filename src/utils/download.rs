@@ -1,53 +1,425 @@
 use anyhow::{Result, Context};
-use indicatif::{ProgressBar, ProgressStyle};
-use reqwest::blocking::Client;
-use std::fs::File;
-use std::io::Write;
-use std::path::Path;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use reqwest::StatusCode;
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use serde_json::json;
+use crate::config::{self, NodeSparkDirs};
+use crate::options::events;
+use crate::options::symbols;
+use crate::options::theme::{self, Role, Themed};
+use crate::options::verbose;
+use crate::utils::{self, http};
+use crate::utils::channel::Channel;
+use crate::utils::platform::Platform;
+use crate::say;
 
-pub fn download_file(url: &str, dest_path: &Path) -> Result<()> {
-    println!("Downloading from {}", url);
+pub fn download_file(url: &str, dest_path: &Path, progress: Option<&MultiProgress>) -> Result<()> {
+    say!("Downloading from {}", url);
+    let started = Instant::now();
+    events::emit("download_started", json!({ "url": url }));
     
-    let client = Client::new();
-    let resp = client.get(url)
+    let client = http::client()?;
+    let mut resp = client.get(url)
         .send()
-        .context("Failed to send request")?;
+        .context("Failed to send request")?
+        .error_for_status()?;
     
-    let total_size = resp.content_length().unwrap_or(0);
+    let pb = byte_progress_bar(resp.content_length(), progress);
     
-    let pb = ProgressBar::new(total_size);
-    pb.set_style(ProgressStyle::default_bar()
-        .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
-        .unwrap()
-        .progress_chars("#>-"));
-    
-    let mut file = File::create(dest_path)?;
-    let content = resp.bytes()?;
-    file.write_all(&content)?;
+    let mut file = BufWriter::new(File::create(dest_path)?);
+    let mut buffer = [0u8; 64 * 1024];
+    let mut downloaded = 0u64;
+    loop {
+        let read = resp.read(&mut buffer).context("Failed while downloading")?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..read])?;
+        downloaded += read as u64;
+        pb.set_position(downloaded);
+    }
+    file.flush()?;
     pb.finish_with_message("Download complete");
+    events::emit("download_finished", json!({
+        "url": url,
+        "bytes": downloaded,
+        "duration_ms": events::elapsed_ms(started),
+    }));
     
     Ok(())
 }
 
-pub fn get_available_versions() -> Result<Vec<String>> {
-    let client = Client::new();
-    let resp = client.get("https://nodejs.org/dist/index.json")
-        .send()
+pub fn byte_progress_bar(total_size: Option<u64>, progress: Option<&MultiProgress>) -> ProgressBar {
+    let pb = match total_size {
+        Some(total_size) => {
+            let pb = ProgressBar::new(total_size);
+            pb.set_style(ProgressStyle::default_bar()
+                .template(&format!(
+                    "{{spinner:.{}}} [{{elapsed_precise}}] [{{bar:40.{}/{}}}] {{bytes}}/{{total_bytes}} ({{bytes_per_sec}}, {{eta}})",
+                    theme::progress_style(Role::Success), theme::progress_style(Role::Info), theme::progress_style(Role::Muted)
+                ))
+                .unwrap()
+                .tick_strings(symbols::spinner_ticks())
+                .progress_chars(symbols::progress_chars()));
+            pb
+        }
+        None => {
+            let pb = ProgressBar::new_spinner();
+            pb.set_style(ProgressStyle::default_spinner()
+                .template(&format!("{{spinner:.{}}} [{{elapsed_precise}}] {{bytes}} ({{bytes_per_sec}})", theme::progress_style(Role::Success)))
+                .unwrap()
+                .tick_strings(symbols::spinner_ticks()));
+            pb
+        }
+    };
+    match progress {
+        Some(progress) => progress.add(pb),
+        None => pb,
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteVersion {
+    #[serde(deserialize_with = "deserialize_version")]
+    pub version: String,
+    #[serde(default, deserialize_with = "deserialize_lts")]
+    pub lts: Option<String>,
+    #[serde(default)]
+    pub date: String,
+    #[serde(default)]
+    pub security: bool,
+    #[serde(default)]
+    pub npm: Option<String>,
+    #[serde(default)]
+    pub v8: Option<String>,
+    #[serde(default)]
+    pub openssl: Option<String>,
+    #[serde(default)]
+    pub modules: Option<String>,
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+fn deserialize_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let version = String::deserialize(deserializer)?;
+    Ok(version.trim_start_matches('v').to_string())
+}
+
+fn deserialize_lts<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(name) => Ok(Some(name)),
+        _ => Ok(None),
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexValidators {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+pub fn get_remote_index() -> Result<Vec<RemoteVersion>> {
+    get_channel_index(Channel::Release)
+}
+
+pub fn index_for(spec: &str) -> Result<Vec<RemoteVersion>> {
+    get_channel_index(Channel::for_spec(spec))
+}
+
+pub fn get_channel_index(channel: Channel) -> Result<Vec<RemoteVersion>> {
+    let url = format!("{}/index.json", channel.base_url());
+    let dirs = config::get_dirs().ok();
+    
+    match fetch_remote_index(channel, &url, dirs.as_ref(), true) {
+        Ok(versions) => Ok(versions),
+        Err(e) => match cached_channel_index(channel) {
+            Some(versions) => {
+                say!("{} could not refresh the version index ({}), using the cached copy", "Warning:".warning(), e);
+                Ok(versions)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+fn fetch_remote_index(channel: Channel, url: &str, dirs: Option<&NodeSparkDirs>, conditional: bool) -> Result<Vec<RemoteVersion>> {
+    // Only revalidate a cached copy that can actually be read back on a 304
+    let cached = dirs.filter(|_| conditional).and_then(|dirs| read_index(dirs, channel));
+    let validators = dirs
+        .filter(|_| cached.is_some())
+        .and_then(|dirs| fs::read(index_validators_path(dirs, channel)).ok())
+        .and_then(|body| serde_json::from_slice::<IndexValidators>(&body).ok())
+        .filter(|validators| validators.url == url)
+        .unwrap_or_default();
+    
+    let mut request = http::client()?.get(url);
+    if let Some(ref etag) = validators.etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(ref last_modified) = validators.last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+    
+    let resp = request.send()
         .context("Failed to fetch available Node.js versions")?;
     
-    let versions: Vec<serde_json::Value> = resp.json()?;
+    if resp.status() == StatusCode::NOT_MODIFIED {
+        match cached {
+            Some(versions) => {
+                verbose::log("Version index not modified, using the cached copy");
+                return Ok(versions);
+            }
+            None => {
+                verbose::log("Version index not modified but the cached copy is gone, fetching it again");
+                return fetch_remote_index(channel, url, dirs, false);
+            }
+        }
+    }
+    let resp = resp.error_for_status()?;
     
-    let mut result = Vec::new();
-    for version in versions {
-        if let Some(version_str) = version["version"].as_str() {
-            let cleaned_version = version_str.trim_start_matches('v').to_string();
-            result.push(cleaned_version);
+    let header = |name| resp.headers().get(name).and_then(|value| value.to_str().ok()).map(String::from);
+    let validators = IndexValidators {
+        url: url.to_string(),
+        etag: header(ETAG),
+        last_modified: header(LAST_MODIFIED),
+    };
+    
+    let Some(dirs) = dirs else {
+        return parse_index(BufReader::new(resp));
+    };
+    
+    // The body is copied to the cache while it is parsed, and only replaces it once it parsed
+    let cache = index_cache_path(dirs, channel);
+    let temp = temp_path(&cache);
+    let copy = File::create(&temp).ok().map(BufWriter::new);
+    let mut reader = BufReader::new(Tee { reader: resp, copy });
+    let versions = match parse_index(&mut reader) {
+        Ok(versions) => versions,
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            return Err(e);
         }
+    };
+    
+    // Drop the old validators first so an interrupted update never pairs them with a different index
+    let meta = index_validators_path(dirs, channel);
+    let _ = fs::remove_file(&meta);
+    let copied = reader.into_inner().copy.is_some_and(|copy| copy.into_inner().is_ok());
+    if copied && fs::rename(&temp, &cache).is_ok() {
+        let _ = write_atomic(&meta, &serde_json::to_vec(&validators)?);
+    } else {
+        let _ = fs::remove_file(&temp);
+    }
+    
+    Ok(versions)
+}
+
+fn parse_index(reader: impl Read) -> Result<Vec<RemoteVersion>> {
+    serde_json::from_reader(reader).context("Failed to parse the Node.js version index")
+}
+
+// Copies everything read through it; a failed write only gives up the copy, never the read
+struct Tee<R, W> {
+    reader: R,
+    copy: Option<W>,
+}
+
+impl<R: Read, W: Write> Read for Tee<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        if let Some(ref mut copy) = self.copy {
+            if copy.write_all(&buf[..read]).is_err() {
+                self.copy = None;
+            }
+        }
+        Ok(read)
+    }
+}
+
+pub fn cached_remote_index() -> Option<Vec<RemoteVersion>> {
+    cached_channel_index(Channel::Release)
+}
+
+pub fn cached_channel_index(channel: Channel) -> Option<Vec<RemoteVersion>> {
+    read_index(&config::get_dirs().ok()?, channel)
+}
+
+fn read_index(dirs: &NodeSparkDirs, channel: Channel) -> Option<Vec<RemoteVersion>> {
+    let body = fs::read(index_cache_path(dirs, channel)).ok()?;
+    serde_json::from_slice(&body).ok()
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    PathBuf::from(temp)
+}
+
+fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let temp = temp_path(path);
+    fs::write(&temp, contents)?;
+    let result = fs::rename(&temp, path);
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
     }
+    result
+}
+
+fn index_cache_path(dirs: &NodeSparkDirs, channel: Channel) -> PathBuf {
+    match channel {
+        Channel::Release => dirs.config_dir.join("index.json"),
+        _ => dirs.config_dir.join(format!("index-{}.json", channel.name())),
+    }
+}
+
+fn index_validators_path(dirs: &NodeSparkDirs, channel: Channel) -> PathBuf {
+    match channel {
+        Channel::Release => dirs.config_dir.join("index.meta.json"),
+        _ => dirs.config_dir.join(format!("index-{}.meta.json", channel.name())),
+    }
+}
+
+pub fn get_available_versions() -> Result<Vec<String>> {
+    Ok(get_remote_index()?
+        .into_iter()
+        .map(|remote| remote.version)
+        .collect())
+}
+
+pub fn fetch_bytes(url: &str) -> Result<Vec<u8>> {
+    let bytes = http::client()?.get(url)
+        .send()
+        .and_then(|resp| resp.error_for_status())
+        .and_then(|resp| resp.bytes())
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    Ok(bytes.to_vec())
+}
+
+pub fn shasums_url(version: &str) -> String {
+    format!("{}/v{}/SHASUMS256.txt", Channel::of(version).base_url(), version)
+}
+
+pub fn fetch_text(url: &str) -> Result<String> {
+    String::from_utf8(fetch_bytes(url)?).with_context(|| format!("{} is not valid UTF-8", url))
+}
+
+pub fn checksum_for(shasums: &str, file_name: &str) -> Option<String> {
+    shasums.lines()
+        .filter_map(|line| line.split_once(char::is_whitespace))
+        .find(|(_, name)| name.trim() == file_name)
+        .map(|(hash, _)| hash.to_string())
+}
+
+pub fn missing_security_release<'a>(version: &str, index: &'a [RemoteVersion]) -> Option<&'a RemoteVersion> {
+    let installed = semver::Version::parse(version).ok()?;
     
-    Ok(result)
+    index.iter()
+        .filter(|remote| remote.security)
+        .filter_map(|remote| semver::Version::parse(&remote.version).ok().map(|parsed| (remote, parsed)))
+        .filter(|(_, parsed)| parsed.major == installed.major && *parsed > installed)
+        .max_by(|a, b| a.1.cmp(&b.1))
+        .map(|(remote, _)| remote)
 }
 
-//pub fn is_lts_version(version_data: &serde_json::Value) -> bool {
-//    version_data.get("lts").map_or(false, |v| !v.is_null())
-//}
+pub fn missing_artifact(version: &str, platform: &Platform) -> Option<Vec<String>> {
+    if platform.is_musl() {
+        return None;
+    }
+    let index = get_channel_index(Channel::of(version)).ok()?;
+    let remote = index.iter().find(|remote| remote.version == version)?;
+    if remote.files.is_empty() || remote.files.contains(&platform.index_file()) {
+        return None;
+    }
+    Some(remote.files.clone())
+}
+
+pub fn newest_in_major<'a>(version: &str, index: &'a [RemoteVersion]) -> Option<&'a RemoteVersion> {
+    let major = utils::major_version(version)?;
+    index.iter().find(|remote| utils::major_version(&remote.version) == Some(major))
+}
+
+pub fn is_alias(spec: &str) -> bool {
+    let spec = spec.to_lowercase();
+    spec == "latest" || spec == "node" || spec == "lts" || spec.starts_with("lts/")
+        || spec == "nightly" || spec.starts_with("nightly-") || spec == "rc"
+}
+
+pub fn remote_matches(spec: &str, remote: &RemoteVersion) -> bool {
+    let spec = spec.to_lowercase();
+    match spec.as_str() {
+        "latest" | "node" => true,
+        "lts" | "lts/*" => remote.lts.is_some(),
+        "nightly" => Channel::of(&remote.version) == Channel::Nightly,
+        "rc" => Channel::of(&remote.version) == Channel::Rc,
+        _ if spec.starts_with("nightly-") => remote.version.split_once("-nightly")
+            .is_some_and(|(_, build)| build.starts_with(&spec["nightly-".len()..])),
+        _ => match spec.strip_prefix("lts/") {
+            Some(codename) => remote.lts.as_deref().is_some_and(|lts| lts.eq_ignore_ascii_case(codename)),
+            None => utils::version_satisfies(spec.trim_start_matches('v'), &remote.version),
+        },
+    }
+}
+
+pub fn resolve_remote(spec: &str, index: &[RemoteVersion]) -> Option<String> {
+    index.iter()
+        .find(|remote| remote_matches(spec, remote))
+        .map(|remote| remote.version.clone())
+}
+
+// The newest installed version a channel or codename spec (lts/*, lts/iron, latest) points at
+pub fn installed_in_channel(channel: &str, installed: &[String], index: &[RemoteVersion]) -> Option<String> {
+    let channel = channel.to_lowercase();
+    if channel == "latest" || channel == "node" {
+        return installed.iter().find(|version| Channel::of(version) == Channel::Release).cloned();
+    }
+    installed.iter()
+        .find(|version| index.iter().any(|remote| &remote.version == *version && remote_matches(&channel, remote)))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> Vec<RemoteVersion> {
+        serde_json::from_str(r#"[
+            {"version": "v21.6.0", "lts": false},
+            {"version": "v20.11.1", "lts": "Iron"},
+            {"version": "v20.11.0", "lts": "Iron"},
+            {"version": "v18.19.0", "lts": "Hydrogen"}
+        ]"#).unwrap()
+    }
+
+    fn versions(list: &[&str]) -> Vec<String> {
+        list.iter().map(|version| version.to_string()).collect()
+    }
+
+    #[test]
+    fn resolves_channels_and_codenames_to_installed_versions() {
+        let installed = versions(&["22.0.0-nightly20240101abc", "21.6.0", "20.11.0", "18.19.0"]);
+        for (spec, expected) in [
+            ("lts/*", Some("20.11.0")),
+            ("lts", Some("20.11.0")),
+            ("lts/iron", Some("20.11.0")),
+            ("LTS/Hydrogen", Some("18.19.0")),
+            ("lts/gallium", None),
+            ("node", Some("21.6.0")),
+            ("latest", Some("21.6.0")),
+        ] {
+            assert_eq!(installed_in_channel(spec, &installed, &index()).as_deref(), expected, "{}", spec);
+        }
+    }
+
+    #[test]
+    fn versions_missing_from_the_index_never_match_a_codename() {
+        let installed = versions(&["20.12.0"]);
+        assert_eq!(installed_in_channel("lts/*", &installed, &index()), None);
+        assert_eq!(installed_in_channel("lts/*", &installed, &[]), None);
+    }
+}
@@ -0,0 +1,143 @@
+use anyhow::{Result, Context, anyhow};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::options::verbose;
+
+pub fn load(path: &Path) -> Result<Vec<(String, String)>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read env file {}", path.display()))?;
+
+    let mut vars = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line.split_once('=').ok_or_else(|| {
+            anyhow!("Invalid line {} in {}: expected KEY=VALUE", number + 1, path.display())
+        })?;
+
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(anyhow!("Invalid variable name on line {} in {}", number + 1, path.display()));
+        }
+
+        vars.push((key.to_string(), parse_value(value.trim())));
+    }
+
+    Ok(vars)
+}
+
+// Later files override earlier ones, keeping the position of the first definition
+pub fn load_all(paths: &[PathBuf]) -> Result<Vec<(String, String)>> {
+    let mut vars: Vec<(String, String)> = Vec::new();
+    for path in paths {
+        verbose::log(&format!("Loading environment from {}", path.display()));
+        for (key, value) in load(path)? {
+            match vars.iter_mut().find(|(existing, _)| *existing == key) {
+                Some(var) => var.1 = value,
+                None => vars.push((key, value)),
+            }
+        }
+    }
+    Ok(vars)
+}
+
+fn parse_value(value: &str) -> String {
+    if let Some(rest) = value.strip_prefix('"') {
+        if let Some(end) = rest.rfind('"') {
+            return unescape(&rest[..end]);
+        }
+    }
+
+    if let Some(rest) = value.strip_prefix('\'') {
+        if let Some(end) = rest.rfind('\'') {
+            return rest[..end].to_string();
+        }
+    }
+
+    match value.find(" #") {
+        Some(index) => value[..index].trim_end().to_string(),
+        None => value.to_string(),
+    }
+}
+
+// One pass, so an escaped backslash is never read again as the start of another escape
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some(escaped @ ('"' | '\\')) => unescaped.push(escaped),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str, files: &[(&str, &str)]) -> Vec<PathBuf> {
+        let dir = std::env::temp_dir().join(format!("node-spark-env-file-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        files.iter().map(|(file, content)| {
+            let path = dir.join(file);
+            fs::write(&path, content).unwrap();
+            path
+        }).collect()
+    }
+
+    #[test]
+    fn decodes_quoted_escapes_in_one_pass() {
+        let cases = [
+            (r#""line\nbreak""#, "line\nbreak"),
+            (r#""a\\nb""#, "a\\nb"),
+            (r#""say \"hi\"""#, "say \"hi\""),
+            (r#""C:\\tools\\""#, "C:\\tools\\"),
+            (r#""keep \t as is""#, "keep \\t as is"),
+            ("'single \\n stays'", "single \\n stays"),
+            ("plain # comment", "plain"),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(parse_value(raw), expected, "{}", raw);
+        }
+    }
+
+    #[test]
+    fn later_files_override_earlier_ones() {
+        let paths = scratch("override", &[
+            ("base.env", "# shared\nHOST=localhost\nexport PORT=3000\nNAME=\"base app\"\n"),
+            ("local.env", "PORT=4000\nDEBUG='1'\n"),
+        ]);
+
+        let vars = load_all(&paths).unwrap();
+        let expected = [("HOST", "localhost"), ("PORT", "4000"), ("NAME", "base app"), ("DEBUG", "1")];
+        assert_eq!(vars, expected.map(|(key, value)| (key.to_string(), value.to_string())));
+
+        let _ = fs::remove_dir_all(paths[0].parent().unwrap());
+    }
+
+    #[test]
+    fn rejects_lines_without_an_assignment() {
+        let paths = scratch("invalid", &[("bad.env", "GOOD=1\nnot an assignment\n")]);
+
+        let error = load(&paths[0]).unwrap_err().to_string();
+        assert!(error.contains("line 2"), "{}", error);
+
+        let _ = fs::remove_dir_all(paths[0].parent().unwrap());
+    }
+}
@@ -1,24 +1,44 @@
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use indicatif::{MultiProgress, ProgressBar};
 use std::fs;
-use std::path::Path;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use crate::utils::download;
 
-pub fn extract_archive(archive_path: &Path, extract_dir: &Path) -> Result<()> {
+pub fn extract_archive(archive_path: &Path, extract_dir: &Path, progress: Option<&MultiProgress>) -> Result<()> {
     let archive_str = archive_path.to_string_lossy();
+    let file = fs::File::open(archive_path)?;
+    
+    // Progress is measured in compressed bytes consumed, so it tracks the archive size on disk
+    let bar = BarGuard(download::byte_progress_bar(Some(file.metadata()?.len()), progress));
+    let pb = &bar.0;
     
     if archive_str.ends_with(".tar.gz") {
-        extract_tar_gz(archive_path, extract_dir)?;
+        extract_tar(flate2::read::GzDecoder::new(pb.wrap_read(file)), extract_dir)?;
+    } else if archive_str.ends_with(".tar.xz") {
+        extract_tar(xz2::read::XzDecoder::new(pb.wrap_read(file)), extract_dir)?;
     } else if archive_str.ends_with(".zip") {
-        extract_zip(archive_path, extract_dir)?;
+        extract_zip(file, extract_dir, pb)?;
     } else {
-        return Err(anyhow::anyhow!("Unsupported archive format"));
+        return Err(anyhow!("Unsupported archive format"));
     }
+    pb.finish();
     
     Ok(())
 }
 
-fn extract_tar_gz(archive_path: &Path, extract_dir: &Path) -> Result<()> {
-    let file = fs::File::open(archive_path)?;
-    let decompressed = flate2::read::GzDecoder::new(file);
+// Clears the bar on every early return, so a failed extraction leaves no half-drawn bar behind
+struct BarGuard(ProgressBar);
+
+impl Drop for BarGuard {
+    fn drop(&mut self) {
+        if !self.0.is_finished() {
+            self.0.finish_and_clear();
+        }
+    }
+}
+
+fn extract_tar<R: Read>(decompressed: R, extract_dir: &Path) -> Result<()> {
     let mut archive = tar::Archive::new(decompressed);
     
     archive.unpack(extract_dir)?;
@@ -26,24 +46,275 @@ fn extract_tar_gz(archive_path: &Path, extract_dir: &Path) -> Result<()> {
     Ok(())
 }
 
-fn extract_zip(archive_path: &Path, extract_dir: &Path) -> Result<()> {
-    let file = fs::File::open(archive_path)?;
+fn extract_zip(file: fs::File, extract_dir: &Path, pb: &ProgressBar) -> Result<()> {
     let mut archive = zip::ZipArchive::new(file)?;
     
+    // Links are created after every other entry, like tar does, so nothing is ever written through one
+    let mut links: Vec<(PathBuf, PathBuf)> = Vec::new();
+    
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
-        let outpath = extract_dir.join(file.name());
+        pb.inc(file.compressed_size());
+        let name = match file.enclosed_name() {
+            Some(name) => name.to_path_buf(),
+            None => return Err(anyhow!("Archive entry {} points outside the install directory", file.name())),
+        };
+        if let Some((link, _)) = links.iter().find(|(link, _)| name.starts_with(link)) {
+            return Err(anyhow!("Archive entry {} would be written through the symlink {}", name.display(), link.display()));
+        }
+        let outpath = extract_dir.join(&name);
         
-        if file.name().ends_with('/') {
+        if file.is_dir() {
             fs::create_dir_all(&outpath)?;
-        } else {
-            if let Some(parent) = outpath.parent() {
-                fs::create_dir_all(parent)?;
+            continue;
+        }
+        if let Some(parent) = outpath.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        
+        let mode = file.unix_mode();
+        if mode.is_some_and(|mode| mode & S_IFMT == S_IFLNK) {
+            // Symlink entries store their target as the file contents
+            let mut target = String::new();
+            file.read_to_string(&mut target)?;
+            let target = PathBuf::from(target);
+            if !stays_inside(&name, &target) {
+                return Err(anyhow!("Archive symlink {} -> {} points outside the install directory", name.display(), target.display()));
+            }
+            links.push((name, target));
+            continue;
+        }
+        
+        let mut outfile = fs::File::create(&outpath)?;
+        std::io::copy(&mut file, &mut outfile)?;
+        if let Some(mode) = mode {
+            set_mode(&outpath, mode)?;
+        }
+    }
+    
+    if cfg!(unix) {
+        create_links(extract_dir, &links)
+    } else {
+        copy_links(extract_dir, &links)
+    }
+}
+
+const S_IFMT: u32 = 0o170000;
+const S_IFLNK: u32 = 0o120000;
+
+// Resolves the target lexically from the link's directory; it must not climb above the archive root
+fn stays_inside(link: &Path, target: &Path) -> bool {
+    let mut depth = link.components().count().saturating_sub(1);
+    for component in target.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+fn create_links(extract_dir: &Path, links: &[(PathBuf, PathBuf)]) -> Result<()> {
+    for (link, target) in links {
+        let path = extract_dir.join(link);
+        if path.symlink_metadata().is_ok_and(|metadata| metadata.is_dir()) {
+            return Err(anyhow!("Archive symlink {} conflicts with a directory of the same name", link.display()));
+        }
+        if path.symlink_metadata().is_ok() {
+            fs::remove_file(&path)?;
+        }
+        symlink(target, &path)?;
+    }
+    
+    // A chain of links can still leave the root even when each target looks fine on its own
+    let root = fs::canonicalize(extract_dir)?;
+    for (link, target) in links {
+        if let Ok(resolved) = fs::canonicalize(extract_dir.join(link)) {
+            if !resolved.starts_with(&root) {
+                return Err(anyhow!("Archive symlink {} -> {} resolves outside the install directory", link.display(), target.display()));
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+// Without symlink support, put a copy of what each link points at in its place. Links may point at
+// other links or at directories, so keep going until no more of them can be resolved.
+fn copy_links(extract_dir: &Path, links: &[(PathBuf, PathBuf)]) -> Result<()> {
+    let mut pending: Vec<&(PathBuf, PathBuf)> = links.iter().collect();
+    while !pending.is_empty() {
+        let before = pending.len();
+        let mut unresolved = Vec::new();
+        for entry in pending {
+            let (link, target) = entry;
+            let path = extract_dir.join(link);
+            let source = path.parent().unwrap_or(extract_dir).join(target);
+            if path.starts_with(&source) {
+                return Err(anyhow!("Archive symlink {} -> {} points at a directory containing itself", link.display(), target.display()));
             }
-            let mut outfile = fs::File::create(&outpath)?;
-            std::io::copy(&mut file, &mut outfile)?;
+            if source.exists() {
+                copy_path(&source, &path)?;
+            } else {
+                unresolved.push(entry);
+            }
+        }
+        if unresolved.len() == before {
+            let (link, target) = unresolved[0];
+            return Err(anyhow!("Archive symlink {} -> {} points to nothing in the archive", link.display(), target.display()));
         }
+        pending = unresolved;
     }
     
     Ok(())
 }
+
+fn copy_path(source: &Path, dest: &Path) -> Result<()> {
+    if !source.is_dir() {
+        fs::copy(source, dest)?;
+        return Ok(());
+    }
+    
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        copy_path(&entry.path(), &dest.join(entry.file_name()))?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(not(unix))]
+fn symlink(_target: &Path, _link: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "symlinks are not supported on this platform"))
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode & 0o7777))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::{FileOptions, ZipWriter};
+
+    enum Entry<'a> {
+        File(&'a str, &'a str, u32),
+        Link(&'a str, &'a str),
+    }
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("node-spark-extract-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("out")).unwrap();
+        dir
+    }
+
+    fn extract(dir: &Path, entries: &[Entry]) -> Result<()> {
+        let archive = dir.join("node.zip");
+        let mut writer = ZipWriter::new(fs::File::create(&archive).unwrap());
+        for entry in entries {
+            match entry {
+                Entry::File(name, contents, mode) => {
+                    writer.start_file(*name, FileOptions::default().unix_permissions(*mode)).unwrap();
+                    writer.write_all(contents.as_bytes()).unwrap();
+                }
+                Entry::Link(name, target) => writer.add_symlink(*name, *target, FileOptions::default()).unwrap(),
+            }
+        }
+        writer.finish().unwrap();
+        extract_archive(&archive, &dir.join("out"), None)
+    }
+
+    #[test]
+    fn rejects_absolute_and_escaping_link_targets() {
+        let dir = scratch("escape");
+        for target in ["/etc", "../../..", "bin/../../../outside"] {
+            let err = extract(&dir, &[Entry::Link("node/lib", target)]).unwrap_err();
+            assert!(err.to_string().contains("outside the install directory"), "{}: {}", target, err);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_writes_through_a_link() {
+        let dir = scratch("through");
+        let err = extract(&dir, &[
+            Entry::Link("node/lib", "bin"),
+            Entry::File("node/lib/payload", "x", 0o644),
+        ]).unwrap_err();
+        assert!(err.to_string().contains("written through the symlink"), "{}", err);
+        assert!(!dir.join("out/node/bin/payload").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_link_chains_that_leave_the_root() {
+        let dir = scratch("chain");
+        let err = extract(&dir, &[
+            Entry::Link("node/up", ".."),
+            Entry::Link("node/escape", "up/.."),
+        ]).unwrap_err();
+        assert!(err.to_string().contains("resolves outside"), "{}", err);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn restores_modes_and_links_to_later_entries() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = scratch("links");
+        extract(&dir, &[
+            Entry::Link("node/bin/npm", "../lib/npm-cli.js"),
+            Entry::File("node/lib/npm-cli.js", "npm", 0o644),
+            Entry::File("node/bin/node", "node", 0o755),
+        ]).unwrap();
+
+        let out = dir.join("out/node");
+        assert_eq!(fs::read_to_string(out.join("bin/npm")).unwrap(), "npm");
+        assert!(out.join("bin/npm").symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(fs::metadata(out.join("bin/node")).unwrap().permissions().mode() & 0o777, 0o755);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn copies_link_targets_that_come_later_or_are_directories() {
+        let dir = scratch("copy");
+        let out = dir.join("out");
+        fs::create_dir_all(out.join("node/lib/npm")).unwrap();
+        fs::write(out.join("node/lib/npm/cli.js"), "npm").unwrap();
+        fs::create_dir_all(out.join("node/bin")).unwrap();
+
+        let links = [
+            (PathBuf::from("node/bin/npx"), PathBuf::from("npm")),
+            (PathBuf::from("node/bin/npm"), PathBuf::from("../lib/npm/cli.js")),
+            (PathBuf::from("node/npm"), PathBuf::from("lib/npm")),
+        ];
+        copy_links(&out, &links).unwrap();
+
+        assert_eq!(fs::read_to_string(out.join("node/bin/npx")).unwrap(), "npm");
+        assert_eq!(fs::read_to_string(out.join("node/bin/npm")).unwrap(), "npm");
+        assert_eq!(fs::read_to_string(out.join("node/npm/cli.js")).unwrap(), "npm");
+
+        let err = copy_links(&out, &[(PathBuf::from("node/bin/dangling"), PathBuf::from("missing"))]).unwrap_err();
+        assert!(err.to_string().contains("points to nothing"), "{}", err);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
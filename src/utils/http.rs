@@ -0,0 +1,47 @@
+use anyhow::{Result, anyhow};
+use reqwest::blocking::{Client, ClientBuilder};
+use reqwest::{Certificate, NoProxy, Proxy};
+use std::fs;
+use std::path::PathBuf;
+use crate::config;
+use crate::options::{tls, verbose};
+use crate::options::theme::Themed;
+
+pub fn client() -> Result<Client> {
+    Ok(builder()?.build()?)
+}
+
+pub fn builder() -> Result<ClientBuilder> {
+    let config = config::load_config()?;
+    let mut builder = Client::builder();
+
+    if let Some(ref proxy) = config.proxy {
+        verbose::log(&format!("Using proxy {} from config", proxy));
+        let proxy = Proxy::all(proxy)
+            .map_err(|e| anyhow!("Invalid proxy in config: {}: {}", proxy, e))?
+            .no_proxy(NoProxy::from_env());
+        builder = builder.proxy(proxy);
+    }
+
+    let ca_bundle = tls::ca_bundle().or_else(|| config.ca_bundle.as_ref().map(PathBuf::from));
+    if let Some(ref path) = ca_bundle {
+        verbose::log(&format!("Trusting CA bundle {}", path.display()));
+        let pem = fs::read(path)
+            .map_err(|e| anyhow!("Failed to read CA bundle {}: {}", path.display(), e))?;
+        let certificates = Certificate::from_pem_bundle(&pem)
+            .map_err(|e| anyhow!("Invalid CA bundle {}: {}", path.display(), e))?;
+        if certificates.is_empty() {
+            return Err(anyhow!("CA bundle {} contains no certificates", path.display()));
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    if tls::is_insecure() || config.insecure {
+        verbose::log(&format!("{}", "TLS certificate verification is disabled".warning()));
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    Ok(builder)
+}
@@ -0,0 +1,97 @@
+use anyhow::{Result, Context};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Mutex;
+use crate::options::theme::Themed;
+
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+static CLEANUP_PATHS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+// (current, original) pairs to rename back, for directories that were moved rather than created
+static RESTORE_PATHS: Mutex<Vec<(PathBuf, PathBuf)>> = Mutex::new(Vec::new());
+
+pub fn install_handler() -> Result<()> {
+    ctrlc::set_handler(|| {
+        eprintln!("\n{}", "Interrupted, cleaning up...".warning());
+
+        // Newest first, so a move that happened inside an earlier one is undone before it
+        if let Ok(mut moves) = RESTORE_PATHS.lock() {
+            for (current, original) in moves.drain(..).rev() {
+                restore_path(&current, &original);
+            }
+        }
+
+        if let Ok(mut paths) = CLEANUP_PATHS.lock() {
+            for path in paths.drain(..) {
+                remove_path(&path);
+            }
+        }
+
+        process::exit(INTERRUPTED_EXIT_CODE);
+    })
+    .context("Failed to install Ctrl+C handler")
+}
+
+pub struct CleanupGuard {
+    path: PathBuf,
+}
+
+pub fn guard(path: &Path) -> CleanupGuard {
+    if let Ok(mut paths) = CLEANUP_PATHS.lock() {
+        paths.push(path.to_path_buf());
+    }
+
+    CleanupGuard {
+        path: path.to_path_buf(),
+    }
+}
+
+impl Drop for CleanupGuard {
+    fn drop(&mut self) {
+        if let Ok(mut paths) = CLEANUP_PATHS.lock() {
+            paths.retain(|p| *p != self.path);
+        }
+
+        remove_path(&self.path);
+    }
+}
+
+pub struct RestoreGuard {
+    current: PathBuf,
+    original: PathBuf,
+}
+
+// Register before moving `original` to `current`; an interrupt moves it back until the guard is dropped
+pub fn restore_on_interrupt(current: &Path, original: &Path) -> RestoreGuard {
+    if let Ok(mut moves) = RESTORE_PATHS.lock() {
+        moves.push((current.to_path_buf(), original.to_path_buf()));
+    }
+
+    RestoreGuard {
+        current: current.to_path_buf(),
+        original: original.to_path_buf(),
+    }
+}
+
+impl Drop for RestoreGuard {
+    fn drop(&mut self) {
+        if let Ok(mut moves) = RESTORE_PATHS.lock() {
+            moves.retain(|(current, original)| *current != self.current || *original != self.original);
+        }
+    }
+}
+
+fn restore_path(current: &Path, original: &Path) {
+    if current.exists() && !original.exists() {
+        let _ = fs::rename(current, original);
+    }
+}
+
+fn remove_path(path: &Path) {
+    if path.is_dir() {
+        let _ = fs::remove_dir_all(path);
+    } else if path.exists() {
+        let _ = fs::remove_file(path);
+    }
+}
@@ -1,50 +1,381 @@
+pub mod archive_cache;
+pub mod channel;
+pub mod disk;
 pub mod download;
+pub mod env_file;
 pub mod extract;
+pub mod http;
+pub mod interrupt;
+pub mod npm_globals;
+pub mod permissions;
+pub mod platform;
+pub mod project_version;
+pub mod receipt;
+pub mod schedule;
+pub mod shim;
+pub mod signature;
+pub mod temp;
 
 use anyhow::{Result, anyhow};
-use semver::Version;
+use semver::{Version, VersionReq};
+use std::fs;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn parse_version(version: &str) -> Result<String> {
-    if let Ok(_) = Version::parse(version) {
+    if Version::parse(version).is_ok() {
         return Ok(version.to_string());
     }
     
-    if version.starts_with('v') {
-        if let Ok(_) = Version::parse(&version[1..]) {
-            return Ok(version[1..].to_string());
+    if let Some(stripped) = version.strip_prefix('v') {
+        if Version::parse(stripped).is_ok() {
+            return Ok(stripped.to_string());
         }
     }
 
     Err(anyhow!("Invalid version format: {}", version))
 }
 
-pub fn get_download_url(version: &str) -> String {
-    let arch = if cfg!(target_arch = "x86_64") {
+#[derive(Debug, PartialEq, Eq)]
+pub enum VersionSpec {
+    Exact(String),
+    // A partial version or a range, matched with version_satisfies
+    Requirement(String),
+}
+
+impl VersionSpec {
+    pub fn matches(&self, version: &str) -> bool {
+        match self {
+            VersionSpec::Exact(exact) => exact == version,
+            VersionSpec::Requirement(spec) => version_satisfies(spec, version),
+        }
+    }
+
+    pub fn resolve(&self, versions: &[String]) -> Option<String> {
+        match self {
+            VersionSpec::Exact(exact) => versions.contains(exact).then(|| exact.clone()),
+            VersionSpec::Requirement(spec) => resolve_spec(spec, versions),
+        }
+    }
+}
+
+pub fn parse_version_spec(spec: &str) -> Result<VersionSpec> {
+    if let Ok(version) = parse_version(spec) {
+        return Ok(VersionSpec::Exact(version));
+    }
+
+    // Partial versions such as "20" or "v20.11" parse as ranges too
+    parse_range(spec).map_err(|_| anyhow!("Invalid version or range: {}", spec))?;
+    Ok(VersionSpec::Requirement(spec.trim().to_string()))
+}
+
+pub fn expand_alias(spec: &str) -> String {
+    crate::config::load_config().ok()
+        .and_then(|config| config.aliases.get(spec.trim()).cloned())
+        .unwrap_or_else(|| spec.to_string())
+}
+
+pub const DEFAULT_DIST_URL: &str = "https://nodejs.org/dist";
+
+pub fn dist_url() -> String {
+    let mirror = env::var("NODE_MIRROR").ok()
+        .filter(|mirror| !mirror.trim().is_empty())
+        .or_else(|| crate::config::load_config().ok().and_then(|config| config.dist_mirror));
+
+    mirror.as_deref()
+        .unwrap_or(DEFAULT_DIST_URL)
+        .trim()
+        .trim_end_matches('/')
+        .to_string()
+}
+
+pub fn host_os() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "win"
+    } else if cfg!(target_os = "macos") {
+        "darwin"
+    } else {
+        "linux"
+    }
+}
+
+pub fn host_arch() -> &'static str {
+    if cfg!(target_arch = "x86_64") {
         "x64"
     } else if cfg!(target_arch = "x86") {
         "x86"
     } else if cfg!(target_arch = "aarch64") {
         "arm64"
+    } else if cfg!(target_arch = "arm") {
+        "armv7l"
+    } else if cfg!(all(target_arch = "powerpc64", target_endian = "little")) {
+        "ppc64le"
+    } else if cfg!(target_arch = "powerpc64") {
+        "ppc64"
+    } else if cfg!(target_arch = "s390x") {
+        "s390x"
     } else {
-        "x64" 
-    };
+        // No Node.js builds exist for this architecture; the install reports that instead of fetching an x64 build
+        env::consts::ARCH
+    }
+}
 
-    let os = if cfg!(target_os = "windows") {
-        "win"
-    } else if cfg!(target_os = "macos") {
-        "darwin"
+pub fn normalize_os(os: &str) -> Result<&'static str> {
+    match os.to_lowercase().as_str() {
+        "linux" => Ok("linux"),
+        "darwin" | "macos" | "mac" => Ok("darwin"),
+        "win" | "windows" => Ok("win"),
+        "aix" => Ok("aix"),
+        _ => Err(anyhow!("Unsupported target OS: {} (expected linux, darwin, win or aix)", os)),
+    }
+}
+
+pub fn normalize_arch(arch: &str) -> Result<&'static str> {
+    match arch.to_lowercase().as_str() {
+        "x64" | "x86_64" | "amd64" => Ok("x64"),
+        "arm64" | "aarch64" => Ok("arm64"),
+        "x86" | "ia32" | "i686" => Ok("x86"),
+        "armv7l" | "armv7" | "armhf" | "arm" => Ok("armv7l"),
+        "ppc64le" => Ok("ppc64le"),
+        "ppc64" => Ok("ppc64"),
+        "s390x" => Ok("s390x"),
+        _ => Err(anyhow!("Unsupported target architecture: {}", arch)),
+    }
+}
+
+pub fn get_headers_url(version: &str) -> String {
+    format!("{}/v{}/node-v{}-headers.tar.gz", channel::Channel::of(version).base_url(), version, version)
+}
+
+pub fn major_version(version: &str) -> Option<u64> {
+    Version::parse(version).ok().map(|v| v.major)
+}
+
+pub fn installed_npm_version(version_dir: &Path) -> Option<String> {
+    let package_json = if cfg!(target_os = "windows") {
+        version_dir.join("node_modules").join("npm").join("package.json")
     } else {
-        "linux"
+        version_dir.join("lib").join("node_modules").join("npm").join("package.json")
+    };
+
+    let content = fs::read_to_string(package_json).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    package["version"].as_str().map(|v| v.to_string())
+}
+
+pub fn version_matches(spec: &str, version: &str) -> bool {
+    let spec = spec.trim_start_matches('v');
+    if version.contains('-') && !spec.contains('-') {
+        return false;
+    }
+    let spec_parts: Vec<&str> = spec.split('.').collect();
+    let version_parts: Vec<&str> = version.split('.').collect();
+
+    spec_parts.len() <= version_parts.len()
+        && spec_parts.iter().zip(version_parts.iter()).all(|(a, b)| a == b)
+}
+
+pub fn version_satisfies(spec: &str, version: &str) -> bool {
+    // Prereleases only match specs that ask for one, e.g. "22.0.0-rc.1" or ">=23.0.0-0"
+    if version.contains('-') && !spec.contains('-') {
+        return false;
+    }
+    if version_matches(spec, version) {
+        return true;
+    }
+
+    let version = match Version::parse(version) {
+        Ok(version) => version,
+        Err(_) => return false,
+    };
+
+    parse_range(spec)
+        .map(|reqs| reqs.iter().any(|req| req.matches(&version)))
+        .unwrap_or(false)
+}
+
+pub fn parse_range(spec: &str) -> Result<Vec<VersionReq>> {
+    spec.split("||")
+        .map(|part| {
+            let tokens: Vec<&str> = part.split_whitespace().collect();
+            let mut comparators = Vec::new();
+            let mut i = 0;
+
+            while i < tokens.len() {
+                if i + 2 < tokens.len() && tokens[i + 1] == "-" {
+                    comparators.push(format!(">={}", tokens[i].trim_start_matches('v')));
+                    comparators.push(format!("<={}", tokens[i + 2].trim_start_matches('v')));
+                    i += 3;
+                    continue;
+                }
+
+                let mut token = tokens[i].to_string();
+                if token.chars().all(|c| "<>=~^".contains(c)) && i + 1 < tokens.len() {
+                    i += 1;
+                    token.push_str(tokens[i]);
+                }
+
+                let operator_len = token.chars().take_while(|c| "<>=~^".contains(*c)).count();
+                let (operator, version) = token.split_at(operator_len);
+                let version = version.trim_start_matches('v');
+
+                if operator.is_empty() && version.starts_with(|c: char| c.is_ascii_digit()) {
+                    comparators.push(format!("={}", version));
+                } else {
+                    comparators.push(format!("{}{}", operator, version));
+                }
+                i += 1;
+            }
+
+            if comparators.is_empty() {
+                comparators.push("*".to_string());
+            }
+
+            VersionReq::parse(&comparators.join(", "))
+                .map_err(|e| anyhow!("Invalid version range '{}': {}", spec, e))
+        })
+        .collect()
+}
+
+pub fn resolve_spec(spec: &str, versions: &[String]) -> Option<String> {
+    versions.iter()
+        .find(|version| version_satisfies(spec, version))
+        .cloned()
+}
+
+pub fn installed_versions(versions_dir: &Path) -> Result<Vec<String>> {
+    let entries = match fs::read_dir(versions_dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
     };
 
-    let ext = if cfg!(target_os = "windows") {
-        "zip"
+    let mut versions = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if entry.path().is_dir() {
+            if let Some(name) = entry.file_name().to_str() {
+                if name.starts_with('.') {
+                    continue;
+                }
+                versions.push(name.to_string());
+            }
+        }
+    }
+
+    sort_versions_desc(&mut versions);
+    Ok(versions)
+}
+
+// Installed versions without nightly builds, which sort among releases but belong to no release line
+pub fn installed_releases(versions_dir: &Path) -> Result<Vec<String>> {
+    let mut versions = installed_versions(versions_dir)?;
+    versions.retain(|version| channel::Channel::of(version) != channel::Channel::Nightly);
+    Ok(versions)
+}
+
+pub fn sort_versions_desc(versions: &mut [String]) {
+    versions.sort_by(|a, b| {
+        match (Version::parse(a), Version::parse(b)) {
+            (Ok(a_ver), Ok(b_ver)) => a_ver.cmp(&b_ver).reverse(),
+            (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+            (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+            (Err(_), Err(_)) => a.cmp(b).reverse()
+        }
+    });
+}
+
+pub fn is_on_path(dir: &Path) -> bool {
+    env::var_os("PATH")
+        .map(|paths| env::split_paths(&paths).any(|p| p == dir))
+        .unwrap_or(false)
+}
+
+pub fn first_on_path(name: &str) -> Option<PathBuf> {
+    let file = if cfg!(target_os = "windows") { format!("{}.exe", name) } else { name.to_string() };
+    env::var_os("PATH")
+        .and_then(|paths| env::split_paths(&paths).find(|dir| dir.join(&file).exists()))
+}
+
+pub fn version_bin_dir(version_dir: &Path) -> PathBuf {
+    if cfg!(target_os = "windows") {
+        version_dir.to_path_buf()
     } else {
-        "tar.gz"
+        version_dir.join("bin")
+    }
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+pub fn format_age(timestamp: u64) -> String {
+    let age = now_secs().saturating_sub(timestamp);
+
+    let (value, unit) = match age {
+        0..=59 => return "just now".to_string(),
+        60..=3599 => (age / 60, "minute"),
+        3600..=86399 => (age / 3600, "hour"),
+        _ => (age / 86400, "day"),
     };
 
-    format!(
-        "https://nodejs.org/dist/v{}/node-v{}-{}-{}.{}",
-        version, version, os, arch, ext
-    )
+    if value == 1 {
+        format!("1 {} ago", unit)
+    } else {
+        format!("{} {}s ago", value, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(list: &[&str]) -> Vec<String> {
+        list.iter().map(|version| version.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_exact_versions_and_requirements() {
+        assert_eq!(parse_version_spec("20.11.1").unwrap(), VersionSpec::Exact("20.11.1".to_string()));
+        assert_eq!(parse_version_spec("v20.11.1").unwrap(), VersionSpec::Exact("20.11.1".to_string()));
+        assert_eq!(parse_version_spec("20").unwrap(), VersionSpec::Requirement("20".to_string()));
+        assert_eq!(parse_version_spec(">=18 <21").unwrap(), VersionSpec::Requirement(">=18 <21".to_string()));
+        assert!(parse_version_spec("twenty").is_err());
+    }
+
+    #[test]
+    fn partial_versions_match_by_component() {
+        let spec = parse_version_spec("20.1").unwrap();
+        assert!(spec.matches("20.1.0"));
+        assert!(spec.matches("20.1.7"));
+        assert!(!spec.matches("20.10.0"));
+        assert!(!spec.matches("21.1.0"));
+
+        let installed = versions(&["22.1.0", "20.11.1", "20.1.7", "18.19.0"]);
+        assert_eq!(parse_version_spec("v20").unwrap().resolve(&installed).as_deref(), Some("20.11.1"));
+        assert_eq!(parse_version_spec("19").unwrap().resolve(&installed), None);
+    }
+
+    #[test]
+    fn ranges_pick_the_newest_match() {
+        let installed = versions(&["22.1.0", "20.11.1", "18.19.0", "16.20.2"]);
+        assert_eq!(parse_version_spec(">=18 <21").unwrap().resolve(&installed).as_deref(), Some("20.11.1"));
+        assert_eq!(parse_version_spec("^18.0.0").unwrap().resolve(&installed).as_deref(), Some("18.19.0"));
+        assert_eq!(parse_version_spec("16 - 18").unwrap().resolve(&installed).as_deref(), Some("18.19.0"));
+        assert_eq!(parse_version_spec("<16 || >=22").unwrap().resolve(&installed).as_deref(), Some("22.1.0"));
+    }
+
+    #[test]
+    fn prereleases_only_match_specs_that_ask_for_them() {
+        let versions = versions(&["23.0.0-nightly20241001abc", "22.0.0-rc.1", "21.7.3"]);
+        assert_eq!(parse_version_spec("22").unwrap().resolve(&versions), None);
+        assert_eq!(parse_version_spec(">=21").unwrap().resolve(&versions).as_deref(), Some("21.7.3"));
+        assert_eq!(parse_version_spec("*").unwrap().resolve(&versions).as_deref(), Some("21.7.3"));
+        assert!(parse_version_spec("22.0.0-rc.1").unwrap().matches("22.0.0-rc.1"));
+        assert!(parse_version_spec(">=22.0.0-rc.0").unwrap().matches("22.0.0-rc.1"));
+        assert_eq!(resolve_spec("22", &versions), None);
+    }
 }
@@ -0,0 +1,70 @@
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+use crate::options::verbose;
+use crate::utils;
+
+const BUNDLED_PACKAGES: [&str; 2] = ["npm", "corepack"];
+
+pub fn list(version_dir: &Path) -> Result<Vec<String>> {
+    let output = npm(version_dir)?
+        .args(["ls", "--global", "--depth=0", "--json"])
+        .output()
+        .map_err(|e| anyhow!("Failed to run npm: {}", e))?;
+
+    let tree: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| anyhow!("Failed to read the global packages of {}: {}", version_dir.display(), e))?;
+
+    let mut packages = Vec::new();
+    if let Some(dependencies) = tree["dependencies"].as_object() {
+        for (name, info) in dependencies {
+            let linked = info["resolved"].as_str().is_some_and(|resolved| resolved.starts_with("file:"));
+            if BUNDLED_PACKAGES.contains(&name.as_str()) || linked {
+                continue;
+            }
+            match info["version"].as_str() {
+                Some(version) => packages.push(format!("{}@{}", name, version)),
+                None => packages.push(name.clone()),
+            }
+        }
+    }
+
+    Ok(packages)
+}
+
+pub fn install(version_dir: &Path, packages: &[String]) -> Result<()> {
+    if packages.is_empty() {
+        return Ok(());
+    }
+
+    verbose::log(&format!("Installing global packages into {}: {}", version_dir.display(), packages.join(" ")));
+    let status = npm(version_dir)?
+        .args(["install", "--global"])
+        .args(packages)
+        .status()
+        .map_err(|e| anyhow!("Failed to run npm: {}", e))?;
+
+    if !status.success() {
+        return Err(anyhow!("npm install --global failed with {}", status));
+    }
+    Ok(())
+}
+
+fn npm(version_dir: &Path) -> Result<Command> {
+    let bin_dir = utils::version_bin_dir(version_dir);
+    let program = bin_dir.join(if cfg!(target_os = "windows") { "npm.cmd" } else { "npm" });
+    if !program.exists() {
+        return Err(anyhow!("{} has no npm", version_dir.display()));
+    }
+
+    let mut paths = vec![bin_dir];
+    if let Some(existing) = env::var_os("PATH") {
+        paths.extend(env::split_paths(&existing));
+    }
+
+    let mut command = Command::new(program);
+    command.env("PATH", env::join_paths(paths)?);
+    Ok(command)
+}
@@ -0,0 +1,60 @@
+use anyhow::{Result, anyhow};
+use std::path::Path;
+use crate::config::Config;
+
+pub fn configured_umask(config: &Config) -> Result<Option<u32>> {
+    let value = match config.umask {
+        Some(ref value) => value.trim(),
+        None => return Ok(None),
+    };
+
+    let umask = match value {
+        "group-writable" => 0o002,
+        "world-readable" => 0o022,
+        "private" => 0o077,
+        octal => u32::from_str_radix(octal, 8)
+            .map_err(|_| anyhow!("Invalid umask in config: {}", octal))?,
+    };
+
+    if umask > 0o777 {
+        return Err(anyhow!("Invalid umask in config: {}", value));
+    }
+
+    Ok(Some(umask))
+}
+
+pub fn apply_configured(config: &Config, path: &Path) -> Result<()> {
+    if let Some(umask) = configured_umask(config)? {
+        apply(path, umask)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+pub fn apply(path: &Path, umask: u32) -> Result<()> {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = path.symlink_metadata()?;
+    if metadata.file_type().is_symlink() {
+        return Ok(());
+    }
+
+    let current = metadata.permissions().mode();
+    let base = if metadata.is_dir() || current & 0o111 != 0 { 0o777 } else { 0o666 };
+    fs::set_permissions(path, fs::Permissions::from_mode(base & !umask))?;
+
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path)? {
+            apply(&entry?.path(), umask)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn apply(_path: &Path, _umask: u32) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use clap::ValueEnum;
+use std::fs;
+use crate::config;
+use crate::utils::{self, download, channel::Channel};
+
+pub const DEFAULT_UNOFFICIAL_BUILDS_URL: &str = "https://unofficial-builds.nodejs.org/download";
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flavor {
+    Glibc,
+    Musl,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Platform {
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub flavor: Flavor,
+}
+
+impl Platform {
+    pub fn host() -> Platform {
+        Platform {
+            os: utils::host_os(),
+            arch: utils::host_arch(),
+            flavor: if is_musl() { Flavor::Musl } else { Flavor::Glibc },
+        }
+    }
+
+    pub fn with_arch(self, arch: Option<&'static str>) -> Platform {
+        match arch {
+            Some(arch) => Platform { arch, ..self },
+            None => self,
+        }
+    }
+
+    pub fn with_flavor(self, flavor: Option<Flavor>) -> Platform {
+        match flavor {
+            Some(flavor) => Platform { flavor, ..self },
+            None => self,
+        }
+    }
+
+    pub fn is_musl(&self) -> bool {
+        self.os == "linux" && self.flavor == Flavor::Musl
+    }
+
+    pub fn name(&self) -> String {
+        if self.is_musl() {
+            format!("{}-{}-musl", self.os, self.arch)
+        } else {
+            format!("{}-{}", self.os, self.arch)
+        }
+    }
+
+    // The platform's entry in the `files` list of index.json
+    pub fn index_file(&self) -> String {
+        match self.os {
+            "darwin" => format!("osx-{}-tar", self.arch),
+            "win" => format!("win-{}-zip", self.arch),
+            os => format!("{}-{}", os, self.arch),
+        }
+    }
+
+    // Preferred first: .tar.xz is about a third smaller, .tar.gz exists for every release
+    pub fn archive_extensions(&self) -> &'static [&'static str] {
+        if self.os == "win" { &["zip"] } else { &["tar.xz", "tar.gz"] }
+    }
+
+    pub fn artifact_names(&self, version: &str) -> Vec<String> {
+        self.archive_extensions().iter()
+            .map(|ext| format!("node-v{}-{}.{}", version, self.name(), ext))
+            .collect()
+    }
+
+    // The archive every release ships, for when there is no checksum list to pick from
+    pub fn universal_artifact(&self, version: &str) -> String {
+        let ext = if self.os == "win" { "zip" } else { "tar.gz" };
+        format!("node-v{}-{}.{}", version, self.name(), ext)
+    }
+
+    // The preferred archive the release's SHASUMS256.txt lists, with its checksum
+    pub fn listed_artifact(&self, version: &str, shasums: &str) -> Option<(String, String)> {
+        self.artifact_names(version).into_iter()
+            .find_map(|name| download::checksum_for(shasums, &name).map(|checksum| (name, checksum)))
+    }
+
+    pub fn base_url(&self, version: &str) -> String {
+        let channel = Channel::of(version);
+        if !self.is_musl() {
+            return channel.base_url();
+        }
+
+        // musl builds are only published by the unofficial-builds project
+        let root = config::load_config().ok()
+            .and_then(|config| config.unofficial_builds_url)
+            .unwrap_or_else(|| DEFAULT_UNOFFICIAL_BUILDS_URL.to_string());
+        let channel = if channel == Channel::Release { "release" } else { channel.name() };
+        format!("{}/{}", root.trim_end_matches('/'), channel)
+    }
+
+    pub fn file_url(&self, version: &str, file_name: &str) -> String {
+        format!("{}/v{}/{}", self.base_url(version), version, file_name)
+    }
+
+    pub fn shasums_url(&self, version: &str) -> String {
+        format!("{}/v{}/SHASUMS256.txt", self.base_url(version), version)
+    }
+}
+
+pub fn is_musl() -> bool {
+    if cfg!(target_env = "musl") {
+        return true;
+    }
+    if !cfg!(target_os = "linux") {
+        return false;
+    }
+
+    // The musl dynamic loader (ld-musl-<arch>.so.1) only exists on musl systems such as Alpine
+    fs::read_dir("/lib")
+        .map(|entries| entries.flatten().any(|entry| entry.file_name().to_string_lossy().starts_with("ld-musl-")))
+        .unwrap_or(false)
+}
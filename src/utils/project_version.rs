@@ -0,0 +1,204 @@
+use anyhow::{Result, anyhow};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Value};
+use crate::utils;
+
+pub struct ProjectVersion {
+    pub spec: String,
+    pub source: PathBuf,
+}
+
+pub fn find_in_current_dir() -> Result<Option<ProjectVersion>> {
+    let current_dir = env::current_dir()?;
+    find(&current_dir)
+}
+
+pub const MISE_FILES: [&str; 3] = [".mise.toml", "mise.toml", ".rtx.toml"];
+
+pub const VERSION_FILES: [&str; 2] = [".nvmrc", ".node-version"];
+
+// Aliases written in a project file are expanded here so every command resolves them the same way
+pub fn find(dir: &Path) -> Result<Option<ProjectVersion>> {
+    for ancestor in dir.ancestors() {
+        if let Some(project) = find_in(ancestor)? {
+            let spec = utils::expand_alias(&project.spec);
+            return Ok(Some(ProjectVersion { spec, ..project }));
+        }
+    }
+
+    Ok(None)
+}
+
+fn find_in(dir: &Path) -> Result<Option<ProjectVersion>> {
+    for name in VERSION_FILES {
+        let path = dir.join(name);
+        if path.is_file() {
+            if let Some(spec) = parse_version_file(&fs::read_to_string(&path)?) {
+                return Ok(Some(ProjectVersion { spec, source: path }));
+            }
+        }
+    }
+
+    if let Some(project) = find_mise(dir) {
+        return Ok(Some(project));
+    }
+
+    let package_json = dir.join("package.json");
+    Ok(read_engines_node(&package_json).map(|spec| ProjectVersion { spec, source: package_json }))
+}
+
+fn find_mise(dir: &Path) -> Option<ProjectVersion> {
+    MISE_FILES.iter()
+        .map(|name| dir.join(name))
+        .filter(|path| path.is_file())
+        .find_map(|path| read_mise_node(&path).map(|spec| ProjectVersion { spec, source: path }))
+}
+
+pub fn declarations(dir: &Path) -> Result<Vec<ProjectVersion>> {
+    let mut found = Vec::new();
+
+    for name in VERSION_FILES {
+        let path = dir.join(name);
+        if path.is_file() {
+            if let Some(spec) = parse_version_file(&fs::read_to_string(&path)?) {
+                found.push(ProjectVersion { spec, source: path });
+            }
+        }
+    }
+
+    found.extend(find_mise(dir));
+
+    let package_json = dir.join("package.json");
+    if package_json.is_file() {
+        if let Some(spec) = read_engines_node(&package_json) {
+            found.push(ProjectVersion { spec, source: package_json });
+        }
+    }
+
+    Ok(found)
+}
+
+fn read_engines_node(package_json: &Path) -> Option<String> {
+    let content = fs::read_to_string(package_json).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    package["engines"]["node"].as_str().map(|spec| spec.trim().to_string())
+}
+
+fn read_mise_node(path: &Path) -> Option<String> {
+    let document: DocumentMut = fs::read_to_string(path).ok()?.parse().ok()?;
+    let node = document.get("tools")?.get("node")?;
+
+    let spec = match node {
+        Item::Value(Value::String(spec)) => spec.value().clone(),
+        Item::Value(Value::Array(specs)) => specs.get(0)?.as_str()?.to_string(),
+        Item::Value(Value::InlineTable(table)) => table.get("version")?.as_str()?.to_string(),
+        Item::Table(table) => table.get("version")?.as_str()?.to_string(),
+        _ => return None,
+    };
+
+    let spec = spec.trim().trim_start_matches('v').to_string();
+    (!spec.is_empty()).then_some(spec)
+}
+
+pub fn write_mise_node(path: &Path, spec: &str) -> Result<()> {
+    let mut document: DocumentMut = if path.is_file() {
+        fs::read_to_string(path)?.parse()?
+    } else {
+        DocumentMut::new()
+    };
+
+    if document.get("tools").is_none() {
+        document["tools"] = toml_edit::table();
+    }
+    let tools = document["tools"].as_table_like_mut()
+        .ok_or_else(|| anyhow!("'tools' in {} is not a table, so node cannot be pinned there", path.display()))?;
+    tools.insert("node", toml_edit::value(spec));
+    fs::write(path, document.to_string())?;
+
+    Ok(())
+}
+
+fn parse_version_file(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .find(|line| !line.is_empty())
+        .map(|line| line.trim_start_matches('v').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("node-spark-project-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (file, content) in files {
+            fs::write(dir.join(file), content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn reads_specs_from_each_kind_of_version_file() {
+        let cases = [
+            (".nvmrc", "20.11.1\n", Some("20.11.1")),
+            (".nvmrc", "v20.11.1", Some("20.11.1")),
+            (".nvmrc", "  lts/*  \n", Some("lts/*")),
+            (".nvmrc", "lts/iron\n", Some("lts/iron")),
+            (".nvmrc", "node\n", Some("node")),
+            (".nvmrc", "# pinned for CI\n\n  18 # current LTS\n", Some("18")),
+            (".nvmrc", "# nothing pinned yet\n", None),
+            (".node-version", "v22.1.0\r\n", Some("22.1.0")),
+            (".node-version", "lts/hydrogen", Some("lts/hydrogen")),
+            ("package.json", r#"{ "engines": { "node": " >=18 <21 " } }"#, Some(">=18 <21")),
+            ("package.json", r#"{ "engines": { "npm": ">=10" } }"#, None),
+            ("package.json", "not json", None),
+            (".mise.toml", "[tools]\nnode = \"v20\"\n", Some("20")),
+            ("mise.toml", "[tools]\nnode = [\"22\", \"20\"]\n", Some("22")),
+            (".rtx.toml", "[tools.node]\nversion = \"lts\"\n", Some("lts")),
+        ];
+
+        for (index, (file, content, expected)) in cases.into_iter().enumerate() {
+            let dir = scratch(&format!("case-{}", index), &[(file, content)]);
+            let found = find_in(&dir).unwrap();
+
+            assert_eq!(found.as_ref().map(|project| project.spec.as_str()), expected, "{} containing {:?}", file, content);
+            if let Some(project) = found {
+                assert_eq!(project.source, dir.join(file));
+            }
+            let _ = fs::remove_dir_all(&dir);
+        }
+    }
+
+    #[test]
+    fn version_files_win_over_mise_and_engines() {
+        let dir = scratch("precedence", &[
+            ("package.json", r#"{ "engines": { "node": ">=18" } }"#),
+            (".mise.toml", "[tools]\nnode = \"20\"\n"),
+            (".node-version", "21\n"),
+            (".nvmrc", "lts/*\n"),
+        ]);
+
+        let declared: Vec<String> = declarations(&dir).unwrap().into_iter().map(|project| project.spec).collect();
+        assert_eq!(declared, ["lts/*", "21", "20", ">=18"]);
+        assert_eq!(find_in(&dir).unwrap().map(|project| project.spec).as_deref(), Some("lts/*"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn searches_parent_directories() {
+        let dir = scratch("ancestors", &[(".nvmrc", "v18.19.0\n")]);
+        let nested = dir.join("packages").join("app");
+        fs::create_dir_all(&nested).unwrap();
+
+        let project = find(&nested).unwrap().unwrap();
+        assert_eq!(project.source, dir.join(".nvmrc"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
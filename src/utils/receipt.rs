@@ -0,0 +1,105 @@
+use anyhow::{Result, Context};
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use crate::config::NodeSparkDirs;
+use crate::utils;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Receipt {
+    pub version: String,
+    pub installed_at: u64,
+    pub source: Option<String>,
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
+impl Receipt {
+    pub fn new(version: &str, source: Option<String>, files: BTreeMap<String, String>) -> Self {
+        Self {
+            version: version.to_string(),
+            installed_at: utils::now_secs(),
+            source,
+            files,
+        }
+    }
+}
+
+pub fn load(dirs: &NodeSparkDirs, version: &str) -> Result<Option<Receipt>> {
+    let path = dirs.receipts_dir.join(format!("{}.json", version));
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)?;
+    let receipt = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse receipt {}", path.display()))?;
+    Ok(Some(receipt))
+}
+
+// Only the install date, without building the file hash map that `load` returns
+pub fn installed_at(dirs: &NodeSparkDirs, version: &str) -> Option<u64> {
+    #[derive(Deserialize)]
+    struct Stamp {
+        installed_at: u64,
+    }
+
+    let file = fs::File::open(dirs.receipts_dir.join(format!("{}.json", version))).ok()?;
+    let stamp: Stamp = serde_json::from_reader(io::BufReader::new(file)).ok()?;
+    Some(stamp.installed_at)
+}
+
+pub fn save(dirs: &NodeSparkDirs, receipt: &Receipt) -> Result<()> {
+    let path = dirs.receipts_dir.join(format!("{}.json", receipt.version));
+    fs::write(path, serde_json::to_string_pretty(receipt)?)?;
+    Ok(())
+}
+
+pub fn remove(dirs: &NodeSparkDirs, version: &str) -> Result<()> {
+    let path = dirs.receipts_dir.join(format!("{}.json", version));
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+pub fn hash_tree(root: &Path) -> Result<BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+    hash_dir(root, root, &mut files)?;
+    Ok(files)
+}
+
+fn hash_dir(root: &Path, dir: &Path, files: &mut BTreeMap<String, String>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let metadata = path.symlink_metadata()?;
+        let relative = path.strip_prefix(root)?.to_string_lossy().replace('\\', "/");
+
+        if metadata.is_dir() {
+            hash_dir(root, &path, files)?;
+        } else {
+            files.insert(relative, hash_entry(&path)?);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn hash_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+pub fn hash_entry(path: &Path) -> Result<String> {
+    let metadata = path.symlink_metadata()?;
+    if metadata.file_type().is_symlink() {
+        Ok(format!("link:{}", fs::read_link(path)?.display()))
+    } else {
+        hash_file(path)
+    }
+}
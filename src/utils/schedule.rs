@@ -0,0 +1,139 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use crate::config::{self, NodeSparkDirs};
+use crate::options::verbose;
+use crate::options::theme::Themed;
+use crate::utils::{self, http};
+
+pub const DEFAULT_SCHEDULE_URL: &str = "https://raw.githubusercontent.com/nodejs/Release/main/schedule.json";
+const REFRESH_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseLine {
+    pub start: String,
+    #[serde(default)]
+    pub lts: Option<String>,
+    #[serde(default)]
+    pub maintenance: Option<String>,
+    pub end: String,
+    #[serde(default)]
+    pub codename: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Phase {
+    Current,
+    ActiveLts(String),
+    Maintenance,
+    EndOfLife,
+}
+
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Current => "current",
+            Phase::ActiveLts(_) => "lts",
+            Phase::Maintenance => "maintenance",
+            Phase::EndOfLife => "eol",
+        }
+    }
+
+    pub fn badge(&self) -> String {
+        match self {
+            Phase::Current => "Current".info().to_string(),
+            Phase::ActiveLts(codename) => format!("LTS ({})", codename).success().to_string(),
+            Phase::Maintenance => "Maintenance".warning().to_string(),
+            Phase::EndOfLife => "EOL".error().to_string(),
+        }
+    }
+}
+
+pub struct Schedule {
+    lines: HashMap<String, ReleaseLine>,
+    today: String,
+}
+
+impl Schedule {
+    pub fn phase(&self, version: &str) -> Option<Phase> {
+        let major = utils::major_version(version)?;
+        let line = self.lines.get(&format!("v{}", major))?;
+        let today = self.today.as_str();
+
+        if today < line.start.as_str() {
+            None
+        } else if today >= line.end.as_str() {
+            Some(Phase::EndOfLife)
+        } else if line.maintenance.as_deref().is_some_and(|maintenance| today >= maintenance) {
+            Some(Phase::Maintenance)
+        } else if line.lts.as_deref().is_some_and(|lts| today >= lts) {
+            Some(Phase::ActiveLts(line.codename.clone().unwrap_or_default()))
+        } else {
+            Some(Phase::Current)
+        }
+    }
+}
+
+// Refreshes the cached schedule at most once a day, whether or not the last attempt succeeded
+pub fn load() -> Option<Schedule> {
+    let dirs = config::get_dirs().ok()?;
+    let path = cache_path(&dirs);
+    let checked = attempt_path(&dirs);
+    let recent = [&path, &checked].iter()
+        .filter_map(|path| fs::metadata(path).and_then(|metadata| metadata.modified()).ok())
+        .filter_map(|modified| SystemTime::now().duration_since(modified).ok())
+        .any(|age| age < REFRESH_AFTER);
+
+    if !recent {
+        let _ = fs::write(&checked, b"");
+        match fetch() {
+            Ok(body) => {
+                let _ = fs::write(&path, &body);
+            }
+            Err(e) => verbose::log(&format!("Could not fetch the release schedule: {}", e)),
+        }
+    }
+    cached()
+}
+
+// The last downloaded schedule, however old, without touching the network
+pub fn cached() -> Option<Schedule> {
+    let body = fs::read(cache_path(&config::get_dirs().ok()?)).ok()?;
+    let lines = serde_json::from_slice(&body).ok()?;
+    Some(Schedule { lines, today: today() })
+}
+
+fn fetch() -> anyhow::Result<Vec<u8>> {
+    let url = config::load_config()?.schedule_url
+        .unwrap_or_else(|| DEFAULT_SCHEDULE_URL.to_string());
+    verbose::log(&format!("Fetching the release schedule from {}", url));
+    let body = http::client()?.get(&url).send()?.error_for_status()?.bytes()?;
+    Ok(body.to_vec())
+}
+
+fn cache_path(dirs: &NodeSparkDirs) -> PathBuf {
+    dirs.config_dir.join("schedule.json")
+}
+
+fn attempt_path(dirs: &NodeSparkDirs) -> PathBuf {
+    dirs.config_dir.join("schedule.checked")
+}
+
+fn today() -> String {
+    let days = (utils::now_secs() / 86400) as i64;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
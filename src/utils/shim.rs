@@ -0,0 +1,131 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::error::NodeSparkError;
+
+pub fn remove(link: &Path) -> Result<()> {
+    for path in [link.to_path_buf(), wrapper_path(link)] {
+        if path.symlink_metadata().is_ok() {
+            fs::remove_file(&path)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+pub fn symlink(target: &Path, link: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(target, link).map_err(|e| link_error(link, e))?;
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn symlink(target: &Path, link: &Path) -> Result<()> {
+    std::os::windows::fs::symlink_file(target, link).map_err(|e| link_error(link, e))?;
+    Ok(())
+}
+
+#[cfg(unix)]
+pub fn link_dir(target: &Path, link: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(target, link).map_err(|e| link_error(link, e))?;
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn link_dir(target: &Path, link: &Path) -> Result<()> {
+    std::os::windows::fs::symlink_dir(target, link).map_err(|e| link_error(link, e))?;
+    Ok(())
+}
+
+fn link_error(link: &Path, error: io::Error) -> anyhow::Error {
+    if error.kind() == io::ErrorKind::PermissionDenied {
+        let hint = if cfg!(target_os = "windows") {
+            "enable Developer Mode or run from an elevated prompt"
+        } else {
+            "check the permissions of its directory"
+        };
+        NodeSparkError::Permission(format!("Cannot create link {}: permission denied ({})", link.display(), hint)).into()
+    } else {
+        error.into()
+    }
+}
+
+pub fn remove_dir_link(link: &Path) -> Result<()> {
+    if cfg!(target_os = "windows") {
+        fs::remove_dir(link)?;
+    } else {
+        fs::remove_file(link)?;
+    }
+    Ok(())
+}
+
+pub fn write_wrapper(link: &Path, target: &Path, env: &BTreeMap<String, String>) -> Result<()> {
+    let path = wrapper_path(link);
+    fs::write(&path, wrapper_script(target, env))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(())
+}
+
+// The file a node/npm/npx shim runs; Windows releases ship node.exe, npm.cmd and npx.cmd
+pub fn target(version_bin_dir: &Path, name: &str) -> PathBuf {
+    if cfg!(target_os = "windows") {
+        let file = if name == "node" { "node.exe".to_string() } else { format!("{}.cmd", name) };
+        version_bin_dir.join(file)
+    } else {
+        version_bin_dir.join(name)
+    }
+}
+
+// Symlinks to npm.cmd would break its own path lookup, so Windows always gets wrapper scripts
+pub fn needs_wrapper(env: &BTreeMap<String, String>) -> bool {
+    cfg!(target_os = "windows") || !env.is_empty()
+}
+
+pub fn points_to(link: &Path, target: &Path) -> bool {
+    if let Ok(destination) = fs::read_link(link) {
+        return destination == target && link.exists();
+    }
+
+    fs::read_to_string(wrapper_path(link))
+        .is_ok_and(|script| script.contains(&*target.to_string_lossy()))
+}
+
+fn wrapper_path(link: &Path) -> PathBuf {
+    if cfg!(target_os = "windows") {
+        link.with_extension("cmd")
+    } else {
+        link.to_path_buf()
+    }
+}
+
+fn wrapper_script(target: &Path, env: &BTreeMap<String, String>) -> String {
+    let mut script = String::new();
+
+    if cfg!(target_os = "windows") {
+        script.push_str("@echo off\r\n");
+        for (key, value) in env {
+            script.push_str(&format!("set \"{}={}\"\r\n", key, value));
+        }
+        script.push_str(&format!("\"{}\" %*\r\n", target.display()));
+    } else {
+        script.push_str("#!/bin/sh\n");
+        for (key, value) in env {
+            script.push_str(&format!("export {}={}\n", key, shell_quote(value)));
+        }
+        script.push_str(&format!("exec {} \"$@\"\n", shell_quote(&target.to_string_lossy())));
+    }
+
+    script
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
@@ -0,0 +1,56 @@
+use anyhow::{Result, anyhow};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use crate::error::NodeSparkError;
+use crate::config::NodeSparkDirs;
+use crate::options::verbose;
+use crate::utils::{download, interrupt};
+use crate::say;
+
+const RELEASE_KEYRING_URL: &str = "https://raw.githubusercontent.com/nodejs/release-keys/main/gpg/pubring.kbx";
+
+pub fn verify_shasums(dirs: &NodeSparkDirs, version: &str, shasums: &str) -> Result<()> {
+    let keyring = release_keyring(dirs)?;
+    let signature = download::fetch_bytes(&format!("{}.sig", download::shasums_url(version)))?;
+
+    let temp_dir = dirs.config_dir.join("temp");
+    fs::create_dir_all(&temp_dir)?;
+    let data_path = temp_dir.join(format!("SHASUMS256-v{}.txt", version));
+    let sig_path = temp_dir.join(format!("SHASUMS256-v{}.txt.sig", version));
+    let _data_guard = interrupt::guard(&data_path);
+    let _sig_guard = interrupt::guard(&sig_path);
+    fs::write(&data_path, shasums)?;
+    fs::write(&sig_path, signature)?;
+
+    verbose::log(&format!("Checking signature with keyring {}", keyring.display()));
+    let output = Command::new("gpgv")
+        .arg("--keyring")
+        .arg(&keyring)
+        .arg(&sig_path)
+        .arg(&data_path)
+        .output()
+        .map_err(|e| anyhow!("Signature verification needs gpgv (GnuPG) on PATH: {}", e))?;
+
+    if !output.status.success() {
+        return Err(NodeSparkError::Verification(format!(
+            "SHASUMS256.txt for Node.js {} is not signed by a Node.js release key:\n{}",
+            version,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )).into());
+    }
+
+    Ok(())
+}
+
+fn release_keyring(dirs: &NodeSparkDirs) -> Result<PathBuf> {
+    let path = dirs.config_dir.join("release-keys.kbx");
+    if path.is_file() {
+        return Ok(path);
+    }
+
+    say!("Fetching Node.js release keys...");
+    let keyring = download::fetch_bytes(RELEASE_KEYRING_URL)?;
+    fs::write(&path, keyring)?;
+    Ok(path)
+}
@@ -0,0 +1,62 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use crate::config::NodeSparkDirs;
+
+// Younger leftovers may belong to an install still running in another process
+pub const STALE_AFTER: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeftoverKind {
+    Download,
+    Install,
+    Headers,
+}
+
+impl LeftoverKind {
+    pub fn reason(self) -> &'static str {
+        match self {
+            LeftoverKind::Download => "leftover download",
+            LeftoverKind::Install => "interrupted install",
+            LeftoverKind::Headers => "interrupted headers download",
+        }
+    }
+}
+
+pub struct Leftover {
+    pub path: PathBuf,
+    pub kind: LeftoverKind,
+}
+
+pub fn stale_entries(dirs: &NodeSparkDirs, max_age: Duration) -> Vec<Leftover> {
+    let mut leftovers = Vec::new();
+    let mut collect = |dir: &Path, kind: LeftoverKind, staging_only: bool| {
+        let entries = fs::read_dir(dir).map(|entries| entries.flatten().collect::<Vec<_>>()).unwrap_or_default();
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if staging_only && !name.starts_with(".staging-") {
+                continue;
+            }
+            // The only copy of a version whose reinstall was killed; the next install of it restores it
+            if name.strip_prefix(".staging-replaced-").is_some_and(|version| !dir.join(version).exists()) {
+                continue;
+            }
+            if is_older_than(&entry.path(), max_age) {
+                leftovers.push(Leftover { path: entry.path(), kind });
+            }
+        }
+    };
+
+    collect(&dirs.config_dir.join("temp"), LeftoverKind::Download, false);
+    collect(&dirs.versions_dir, LeftoverKind::Install, true);
+    collect(&dirs.headers_dir, LeftoverKind::Headers, true);
+    leftovers
+}
+
+fn is_older_than(path: &Path, max_age: Duration) -> bool {
+    path.symlink_metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age >= max_age)
+}
@@ -0,0 +1,98 @@
+#![cfg(unix)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn sandbox(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("node-spark-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("home/.config/node-spark")).unwrap();
+    fs::create_dir_all(root.join("project")).unwrap();
+    fs::write(root.join("home/.config/node-spark/config.json"), r#"{"auto_install": true}"#).unwrap();
+    fs::write(root.join("project/.nvmrc"), "20.11.1\n").unwrap();
+    root
+}
+
+fn node_spark(root: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_node-spark"));
+    command.current_dir(root.join("project"))
+        .env("HOME", root.join("home"))
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_DATA_HOME")
+        .env_remove("XDG_CACHE_HOME")
+        // Nothing listens here, so an install attempt would fail loudly
+        .env("NODE_MIRROR", "http://127.0.0.1:9/dist");
+    command
+}
+
+#[test]
+fn env_no_install_prints_nothing_for_a_missing_version() {
+    let root = sandbox("env");
+    let output = node_spark(&root).args(["env", "--shell", "bash", "--no-install"]).output().unwrap();
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty(), "stdout: {}", String::from_utf8_lossy(&output.stdout));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("20.11.1"));
+    assert!(!root.join("home/.local/share/node-spark/versions/20.11.1").exists());
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn bash_hook_stays_valid_shell_for_a_missing_version() {
+    if Command::new("bash").arg("--version").output().is_err() {
+        return;
+    }
+    let root = sandbox("hook");
+    let hook = node_spark(&root).args(["init", "bash"]).output().unwrap();
+    assert!(hook.status.success());
+
+    let script = format!("{}\necho \"hook-status:$?\"\n", String::from_utf8(hook.stdout).unwrap());
+    let output = Command::new("bash")
+        .args(["--norc", "--noprofile", "-c", &script])
+        .current_dir(root.join("project"))
+        .env("HOME", root.join("home"))
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_DATA_HOME")
+        .env("NODE_MIRROR", "http://127.0.0.1:9/dist")
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stdout.contains("hook-status:0"), "stdout: {}\nstderr: {}", stdout, stderr);
+    assert!(!stderr.contains("command not found"), "stderr: {}", stderr);
+    assert!(!stderr.contains("Resolving"), "stderr: {}", stderr);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn env_no_install_resolves_channel_specs_from_the_cached_index() {
+    let root = sandbox("channel");
+    fs::create_dir_all(root.join("home/.local/share/node-spark/versions/20.11.1/bin")).unwrap();
+    fs::write(root.join("home/.config/node-spark/index.json"), r#"[
+        {"version": "v21.6.0", "lts": false},
+        {"version": "v20.11.1", "lts": "Iron"}
+    ]"#).unwrap();
+
+    for spec in ["lts/*", "lts/iron", "node"] {
+        fs::write(root.join("project/.nvmrc"), format!("{}\n", spec)).unwrap();
+        let output = node_spark(&root).args(["env", "--shell", "bash", "--no-install"]).output().unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{}: {}", spec, String::from_utf8_lossy(&output.stderr));
+        assert!(stdout.contains("export NODE_SPARK_VERSION='20.11.1'"), "{}: {}", spec, stdout);
+    }
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn env_refuses_to_guess_other_shells() {
+    let root = sandbox("shell");
+    for shell in ["/bin/tcsh", "/bin/ksh", "/bin/csh"] {
+        let output = node_spark(&root).args(["env", "--no-install"]).env("SHELL", shell).output().unwrap();
+        assert!(!output.status.success(), "{}", shell);
+        assert!(output.stdout.is_empty(), "{}", shell);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Unsupported shell"), "{}", shell);
+    }
+    fs::remove_dir_all(&root).unwrap();
+}